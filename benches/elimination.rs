@@ -0,0 +1,29 @@
+//Times AdmGraph::with_options, run_elimination, the augmenting path searches and compute_ordering on
+//the generated graphs and the networks in the networks folder, for the smallest p and the p below it
+//with each search strategy.
+//Run with cargo bench, an argument only runs the graphs whose name contains it and BENCH_REPEAT sets
+//the number of timed runs
+use admissibility_rust::admGraph::AdmOptions;
+use admissibility_rust::bench::{bench_smallest_p, generated_graphs, to_text};
+use admissibility_rust::load_graph;
+use graphbench::graph::MutableGraph;
+
+const NETWORKS: [&str; 1] = ["karate"];
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let repeat = std::env::var("BENCH_REPEAT").map_or(10, |r| r.parse().expect("BENCH_REPEAT is not a number"));
+
+    let mut graphs = generated_graphs();
+    for network in NETWORKS {
+        let (mut graph, _) = load_graph("networks".to_string(), &network.to_string());
+        graph.remove_loops();
+        graphs.push((network.to_string(), graph));
+    }
+    for (name, graph) in &graphs {
+        if filter.as_ref().is_some_and(|filter| !name.contains(filter.as_str())) {
+            continue;
+        }
+        print!("{}", to_text(&bench_smallest_p(name, graph, repeat, &AdmOptions::default())));
+    }
+}
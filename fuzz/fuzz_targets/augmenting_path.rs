@@ -0,0 +1,90 @@
+#![no_main]
+//Gives both searches an auxiliary graph with any edges, starts and ends, not only the ones AdmGraph
+//builds, and checks that they do not panic and that the edges returned are those of vertex disjoint
+//augmenting paths
+use admissibility_rust::augmentingPath::{AugmentingPath, AuxiliaryGraph, MatchingEdges};
+use arbitrary::Arbitrary;
+use graphbench::graph::{Vertex, VertexMap, VertexSet};
+use libfuzzer_sys::fuzz_target;
+
+//The vertices outside M that starts and ends get matched with are kept apart from the vertices of the
+//auxiliary graph, which are below OUT, as they are in R and L & M for AdmGraph
+const OUT: Vertex = 256;
+const END: Vertex = 512;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    s: Vec<(u8, u8)>, //vertex in s and the vertex outside M it gets matched with, offset by OUT
+    t: Vec<(u8, u8)>, //vertex that can end a path and the vertex outside M it gets matched with, offset by END
+    edges: Vec<(u8, u8)>,
+}
+
+struct FuzzGraph {
+    edges: VertexMap<Vec<Vertex>>,
+    t: VertexMap<Vertex>,
+}
+
+impl AuxiliaryGraph for FuzzGraph {
+    fn next(&self, u: Vertex) -> Vec<Vertex> {
+        self.edges.get(&u).cloned().unwrap_or_default()
+    }
+
+    fn end(&self, w: Vertex) -> Option<Vertex> {
+        self.t.get(&w).copied()
+    }
+}
+
+//Follows each path from the vertex in s that out is matched with, going to the next vertex by e_remove
+//and e_add until e_add matches it with its end. Every step has to be an edge, no vertex or end can be
+//on two paths and every edge returned has to be on a path. Returns the number of paths
+fn assert_paths(aug_path: &AugmentingPath<FuzzGraph>, edges: &MatchingEdges) -> usize {
+    let next_on_path: VertexMap<Vertex> = edges.e_remove.iter().map(|(l, r)| (*r, *l)).collect();
+    assert_eq!(next_on_path.len(), edges.e_remove.len(), "two paths share a vertex");
+
+    let mut seen = VertexSet::default();
+    let mut paths = 0;
+    for (out, first) in edges.e_add.iter().filter(|(l, _)| (OUT..END).contains(*l)) {
+        assert_eq!(aug_path.out.get(first), Some(out), "{out} is not out of {first}");
+        paths += 1;
+        let mut r = *first;
+        loop {
+            assert!(seen.insert(r), "{r} is on two paths");
+            let l = *next_on_path.get(&r).unwrap_or_else(|| panic!("{r} is not removed from M"));
+            assert!(aug_path.aux.next(r).contains(&l), "{r} -> {l} is not an edge");
+            assert!(seen.insert(l), "{l} is on two paths");
+            let next = *edges.e_add.get(&l).unwrap_or_else(|| panic!("{l} is not added to M"));
+            if next >= END {
+                assert_eq!(aug_path.aux.end(l), Some(next), "{l} can not end a path");
+                assert!(seen.insert(next), "{next} ends two paths");
+                break;
+            }
+            assert!(aug_path.aux.next(l).contains(&next), "{l} -> {next} is not an edge");
+            r = next;
+        }
+    }
+    assert_eq!(seen.len(), 2 * edges.e_remove.len() + paths, "e_remove has edges on no path");
+    assert_eq!(edges.e_add.len(), edges.e_remove.len() + paths, "e_add has edges on no path");
+    paths
+}
+
+fuzz_target!(|input: Input| {
+    let mut aux = FuzzGraph {
+        edges: VertexMap::default(),
+        t: input.t.iter().map(|(w, end)| (*w as Vertex, END + *end as Vertex)).collect(),
+    };
+    for (u, w) in &input.edges {
+        aux.edges.entry(*u as Vertex).or_default().push(*w as Vertex);
+    }
+    let mut aug_path = AugmentingPath::new(0, aux);
+    for (v, out) in &input.s {
+        aug_path.s.insert(*v as Vertex);
+        aug_path.out.insert(*v as Vertex, OUT + *out as Vertex);
+    }
+
+    if let Some(edges) = aug_path.find_augmenting_path() {
+        assert_eq!(assert_paths(&aug_path, &edges), 1);
+    }
+    if let Some(edges) = aug_path.find_augmenting_paths() {
+        assert!(assert_paths(&aug_path, &edges) > 0);
+    }
+});
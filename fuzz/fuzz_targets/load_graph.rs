@@ -0,0 +1,60 @@
+#![no_main]
+//Writes the edge list gzipped to a file, loads it with load_graph and checks that the search finds an
+//ordering of the smallest p with the options picked by the input
+use admissibility_rust::admGraph::AdmOptions;
+use admissibility_rust::augmentingPath::SearchStrategy;
+use admissibility_rust::checkOrdering::ordering_p;
+use admissibility_rust::vertexLabels::read_labelled_graph;
+use admissibility_rust::{compute_ordering, compute_ordering_with_options, find_p, load_graph};
+use arbitrary::{Arbitrary, Unstructured};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use graphbench::graph::{Graph, MutableGraph, VertexSet};
+use libfuzzer_sys::fuzz_target;
+use std::io::Write;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    start_p: u8,
+    phase: bool,
+    prune: bool,
+    skip_n1_repair: bool,
+    skip_l2_repair: bool,
+}
+
+//The first bytes pick the options and the rest is the edge list as it is
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let Ok(input) = Input::arbitrary(&mut u) else {
+        return;
+    };
+    let edge_list = u.take_rest();
+    //load_graph panics on a file read_labelled_graph rejects, which is how the command line reports it
+    if read_labelled_graph(edge_list).is_err() {
+        return;
+    }
+    let dir = std::env::temp_dir().join(format!("admissibility-fuzz-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut encoder = GzEncoder::new(std::fs::File::create(dir.join("fuzz.txt.gz")).unwrap(), Compression::fast());
+    encoder.write_all(edge_list).unwrap();
+    encoder.finish().unwrap();
+
+    let (mut graph, labels) = load_graph(dir.to_str().unwrap().to_string(), &"fuzz".to_string());
+    graph.remove_loops();
+    assert_eq!(graph.num_vertices(), labels.len());
+
+    let options = AdmOptions {
+        search: if input.phase { SearchStrategy::Phase } else { SearchStrategy::Dfs },
+        prune_low_degree: input.prune,
+        skip_n1_repair: input.skip_n1_repair,
+        skip_l2_repair: input.skip_l2_repair,
+        ..AdmOptions::default()
+    };
+    let (p, order) = find_p(input.start_p as i32, &graph, true, &options, &mut Vec::new());
+    let order = order.unwrap();
+    assert_eq!(order.len(), graph.num_vertices());
+    assert_eq!(order.iter().copied().collect::<VertexSet>().len(), order.len());
+    assert_eq!(ordering_p(&graph, &order) as i32, p);
+    assert!(p == 0 || compute_ordering(p as usize - 1, &graph, false).is_none());
+    assert!(compute_ordering_with_options(p as usize, &graph, false, &options).is_some());
+});
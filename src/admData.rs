@@ -67,6 +67,24 @@ impl AdmData {
         self.m_from_l.len() + self.n_in_l.len() < p + 1
     }
 
+    //m_from_l and m_from_r should hold the same edges in opposite directions
+    pub fn check_m_is_mirrored(&self) -> Result<(), String> {
+        if self.m_from_l.len() != self.m_from_r.len() {
+            return Err(format!(
+                "M of {} has {} edges from L and {} edges from R",
+                self.id,
+                self.m_from_l.len(),
+                self.m_from_r.len()
+            ));
+        }
+        for (l, r) in &self.m_from_l {
+            if self.m_from_r.get(r) != Some(l) {
+                return Err(format!("M of {} has edge {l}-{r} only from L", self.id));
+            }
+        }
+        Ok(())
+    }
+
     //Checks if a vertex v in L is not in M or not in L1
     //If not that vertex can be added to M
     pub fn can_add_vertex_in_l_to_m(&self, v: &Vertex) -> bool {
@@ -133,6 +151,19 @@ mod test_adm_data {
         assert_eq!(v.n_in_r.len(), 1);
     }
 
+    #[test]
+    fn check_m_is_mirrored_should_find_edges_only_in_one_direction() {
+        let neighbours = [2, 3, 4, 5].iter().cloned().collect();
+        let mut v = AdmData::new(1, neighbours);
+        v.add_edges_to_m(6, 2);
+        assert!(v.check_m_is_mirrored().is_ok());
+
+        v.m_from_r.insert(2, 7);
+        v.m_from_l.insert(7, 3);
+
+        assert!(v.check_m_is_mirrored().is_err());
+    }
+
     #[test]
     fn add_edges_to_m_should_add_edges_in_both_directions() {
         let neighbours = [2, 3, 4, 5].iter().cloned().collect();
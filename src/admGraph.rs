@@ -1,8 +1,118 @@
 use crate::admData::AdmData;
-use crate::augmentingPath::AugmentingPath;
+use crate::augmentingPath::{AugmentingPath, AuxiliaryGraph, MatchingEdges, SearchStrategy};
+use crate::checkOrdering::max_packing;
 use graphbench::editgraph::EditGraph;
 use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+use serde::Serialize;
+use std::cmp::max;
 use std::mem;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AdmOptions {
+    pub prune_low_degree: bool, //move vertices of degree at most p to R before the elimination starts
+    pub search: SearchStrategy, //how augmenting paths are searched for
+    pub skip_n1_repair: bool,   //do not try to add a vertex moving to R to M of its neighbours
+    pub skip_l2_repair: bool,   //do not try to replace an edge of M removed when its vertex in L moves to R
+    pub validate: bool,         //check the invariants of every AdmData after each vertex moves to R
+    pub exact_check: bool,      //compare the value of each checked vertex with a maximum packing computed from scratch
+    pub record_witnesses: bool, //keep M of each vertex when it moves to R in witnesses
+    pub time_searches: bool,    //add up the time spent in AugmentingPath::find in search_time
+}
+
+//A vertex checked by do_checks whose value from the incremental M disagrees with the value from a
+//maximum packing, values above p are capped at p + 1 before comparing as M only grows until then
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Discrepancy {
+    pub vertex: Vertex,
+    pub incremental: usize,
+    pub exact: usize,
+}
+
+//How often each step of the incremental maintenance of M is taken
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AdmStats {
+    pub n1_added: usize,            //a vertex moving to R was added to M of a neighbour by update_n1_of_v
+    pub n1_failed: usize,           //the repair was tried but no 2-path through the vertex could be added
+    pub n1_skipped: usize,          //the repair was not tried as it is disabled
+    pub l2_replaced: usize,         //an edge removed from M by update_l2_of_v was replaced by another edge
+    pub l2_failed: usize,           //the repair was tried but no other edge could replace it
+    pub l2_skipped: usize,          //the repair was not tried as it is disabled
+    pub do_checks: usize,           //calls to do_checks
+    pub unmatched_edges: usize,     //edges outside M added without a search, only looked for when a repair is disabled
+    pub searches: usize,            //augmenting path searches run
+    pub successful_searches: usize, //searches that found a path
+    pub aux_vertices: usize,        //vertices of the auxiliary graphs searched, summed over the searches
+    pub update_m: usize,            //calls to update_m
+    pub peak_candidates: usize,     //largest number of candidates at once
+}
+
+impl AdmStats {
+    pub fn average_aux_vertices(&self) -> f64 {
+        if self.searches == 0 {
+            0.0
+        } else {
+            self.aux_vertices as f64 / self.searches as f64
+        }
+    }
+}
+
+//Auxiliary graph for finding an augmenting path of v. Vertices in R & M go along their matching
+//edge to L, vertices in L & M go along edges of the graph to other vertices in R & M and a path
+//ends at a vertex in L & M with a neighbour in R that is not in M
+struct AdmAuxiliaryGraph<'a> {
+    graph: &'a EditGraph,
+    v: &'a AdmData,
+}
+
+impl AdmAuxiliaryGraph<'_> {
+    //Neighbours of w in R of v, going through whichever of the two sets is smaller so hubs with
+    //large neighbourhoods are not scanned
+    fn neighbours_in_r(&self, w: Vertex) -> Vec<Vertex> {
+        if self.graph.degree(&w) as usize <= self.v.n_in_r.len() {
+            self.graph
+                .neighbours(&w)
+                .filter(|u| self.v.n_in_r.contains(u))
+                .copied()
+                .collect()
+        } else {
+            self.v
+                .n_in_r
+                .iter()
+                .filter(|u| self.graph.adjacent(u, &w))
+                .copied()
+                .collect()
+        }
+    }
+}
+
+impl AuxiliaryGraph for AdmAuxiliaryGraph<'_> {
+    fn next(&self, u: Vertex) -> Vec<Vertex> {
+        if let Some(w) = self.v.m_from_r.get(&u) {
+            return vec![*w];
+        }
+        match self.v.m_from_l.get(&u) {
+            Some(x) => self
+                .neighbours_in_r(u)
+                .into_iter()
+                .filter(|y| y != x && self.v.m_from_r.contains_key(y))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    fn end(&self, w: Vertex) -> Option<Vertex> {
+        if !self.v.m_from_l.contains_key(&w) {
+            return None;
+        }
+        self.neighbours_in_r(w)
+            .into_iter()
+            .find(|y| !self.v.m_from_r.contains_key(y))
+    }
+}
+
+//2-paths v-r-l in M of each vertex v when it moved to R, as (r, l)
+pub type Witnesses = VertexMap<Vec<(Vertex, Vertex)>>;
 
 pub struct AdmGraph<'a> {
     graph: &'a EditGraph,
@@ -11,10 +121,19 @@ pub struct AdmGraph<'a> {
     checks: VertexSet,
     pub candidates: VertexSet,
     adm_data: VertexMap<AdmData>,
+    options: AdmOptions,
+    pub stats: AdmStats,
+    pub discrepancies: Vec<Discrepancy>,
+    pub witnesses: Witnesses,
+    pub search_time: Duration,
 }
 
 impl<'a> AdmGraph<'a> {
     pub fn new(graph: &'a EditGraph) -> Self {
+        AdmGraph::with_options(graph, AdmOptions::default())
+    }
+
+    pub fn with_options(graph: &'a EditGraph, options: AdmOptions) -> Self {
         let mut adm_data = VertexMap::default();
         let l = graph.vertices().copied().collect();
         for u in graph.vertices() {
@@ -28,6 +147,11 @@ impl<'a> AdmGraph<'a> {
             checks: VertexSet::default(),
             candidates: VertexSet::default(),
             adm_data,
+            options,
+            stats: AdmStats::default(),
+            discrepancies: Vec::new(),
+            witnesses: VertexMap::default(),
+            search_time: Duration::ZERO,
         }
     }
 
@@ -37,10 +161,61 @@ impl<'a> AdmGraph<'a> {
                 self.candidates.insert(*u);
             }
         }
+        self.stats.peak_candidates = max(self.stats.peak_candidates, self.candidates.len());
+    }
+
+    //A vertex of degree at most p never has more than p neighbours in L and 2-paths through R,
+    //so all of them can be moved to R before any other vertex. The values of the remaining vertices
+    //only go down as more vertices move to R, so after this the remaining vertices are added to
+    //candidates once their matching is maximum and small enough, instead of using initialise_candidates.
+    //Repeating this on the vertices left, as in a core decomposition, would not be safe as a
+    //vertex with few neighbours left in L can still have more than p 2-paths through the removed vertices.
+    //The AdmData of the pruned vertices is kept, as a 2-path v-x-w of a vertex v left in L can have
+    //its middle vertex x among them and is found through the neighbours in L of x. They never become
+    //candidates or get checked again, so only their neighbours in L are updated from here on
+    pub fn prune_low_degree(&mut self, p: usize) -> Vec<Vertex> {
+        let pruned: Vec<Vertex> = self
+            .l
+            .iter()
+            .filter(|v| self.graph.degree(v) as usize <= p)
+            .copied()
+            .collect();
+
+        for v in &pruned {
+            self.l.remove(v);
+            self.r_count += 1;
+            self.adm_data.get_mut(v).unwrap().delete_m();
+        }
+        for v in &pruned {
+            for u in self.graph.neighbours(v) {
+                self.adm_data.get_mut(u).unwrap().move_v_in_l_to_r(v);
+            }
+        }
+
+        for v in self.l.clone() {
+            let mut v_adm_data = self.adm_data.remove(&v).unwrap();
+            for x in &v_adm_data.n_in_r.clone() {
+                for w in &self.adm_data.get(x).unwrap().n_in_l {
+                    if v_adm_data.can_add_vertex_in_l_to_m(w) {
+                        v_adm_data.add_edges_to_m(*w, *x);
+                        break;
+                    }
+                }
+            }
+            self.grow_m(&mut v_adm_data, p);
+            self.adm_data.insert(v, v_adm_data);
+        }
+        self.stats.peak_candidates = max(self.stats.peak_candidates, self.candidates.len());
+        pruned
+    }
+
+    //Vertices not moved to R yet, when the elimination stalls none of them can be moved
+    pub fn vertices_in_l(&self) -> &VertexSet {
+        &self.l
     }
 
     pub fn is_all_vertices_in_r_or_candidates(&self) -> bool {
-        return self.r_count + self.candidates.len() == self.graph.num_vertices();
+        self.r_count + self.candidates.len() == self.graph.num_vertices()
     }
 
     //When a vertex v is moving into R need to move v from L to R for all of v's neighbours u in L
@@ -52,10 +227,21 @@ impl<'a> AdmGraph<'a> {
             u_adm_data.move_v_in_l_to_r(&v);
 
             if !u_adm_data.deleted_m {
-                for w in &n_in_l {
-                    if u_adm_data.can_add_vertex_in_l_to_m(w) {
-                        u_adm_data.add_edges_to_m(*w, v);
-                        break;
+                if self.options.skip_n1_repair {
+                    self.stats.n1_skipped += 1;
+                } else {
+                    let mut added = false;
+                    for w in &n_in_l {
+                        if u_adm_data.can_add_vertex_in_l_to_m(w) {
+                            u_adm_data.add_edges_to_m(*w, v);
+                            added = true;
+                            break;
+                        }
+                    }
+                    if added {
+                        self.stats.n1_added += 1;
+                    } else {
+                        self.stats.n1_failed += 1;
                     }
                 }
                 if !self.candidates.contains(u) {
@@ -69,7 +255,15 @@ impl<'a> AdmGraph<'a> {
         let mut union_left_neighbours = VertexSet::default();
         let v_adm_data = self.adm_data.get(&v).unwrap();
 
-        for x in v_adm_data.m_from_r.keys() {
+        //A vertex u with v in its M through x is either in M of v through x or it blocks x from being
+        //in M of v, which only holds while the greedy repairs keep M of v maximal
+        let xs: Box<dyn Iterator<Item = &Vertex>> =
+            if self.options.skip_n1_repair || self.options.skip_l2_repair {
+                Box::new(v_adm_data.n_in_r.iter())
+            } else {
+                Box::new(v_adm_data.m_from_r.keys())
+            };
+        for x in xs {
             let v_adm_data = self.adm_data.get(x).unwrap();
             union_left_neighbours.extend(v_adm_data.n_in_l.iter().cloned());
         }
@@ -88,6 +282,9 @@ impl<'a> AdmGraph<'a> {
                 None => {}
                 //check to see if we can replace the edge x,v being removed
                 //by checking if v can be replaced by another vertex in L1 of x
+                Some(_) if self.options.skip_l2_repair => {
+                    self.stats.l2_skipped += 1;
+                }
                 Some(x) => {
                     let x_n_in_l: VertexSet = self
                         .adm_data
@@ -97,12 +294,19 @@ impl<'a> AdmGraph<'a> {
                         .iter()
                         .cloned()
                         .collect();
+                    let mut replaced = false;
                     for y in x_n_in_l {
                         if u_adm_data.can_add_vertex_in_l_to_m(&y) {
                             u_adm_data.add_edges_to_m(y, x);
+                            replaced = true;
                             break;
                         }
                     }
+                    if replaced {
+                        self.stats.l2_replaced += 1;
+                    } else {
+                        self.stats.l2_failed += 1;
+                    }
                 }
             }
             self.adm_data.insert(u, u_adm_data);
@@ -113,59 +317,198 @@ impl<'a> AdmGraph<'a> {
         }
     }
 
-    fn construct_g_for_augmenting_path(&self, v: &mut AdmData) -> AugmentingPath {
-        let mut augmenting_path = AugmentingPath::new(v.id);
-
-        //Get all the edges between vertices in r and vertices in L & M
-        for u in &v.n_in_r {
-            for (w, w_neighbour_in_m) in &v.m_from_l {
-                //Gets matching edges already in M
-                if *u == *w_neighbour_in_m {
-                    augmenting_path.edges.entry(*u).or_default().insert(*w);
-                } else if self.graph.adjacent(u, w) {
-                    //Gets edges between vertices in M (excluding matching edges)
-                    if v.m_from_r.contains_key(u) {
-                        augmenting_path.edges.entry(*w).or_default().insert(*u);
-                    } else {
-                        //Gets edges between a vertex in L and M and a vertex R not in M
-                        augmenting_path.t.insert(*w);
-                        augmenting_path.out.insert(*w, *u);
-                    }
-                }
-            }
-        }
+    //The auxiliary graph is not built here, its edges are found by AdmAuxiliaryGraph when the search
+    //reaches a vertex. Only the start points, vertices u in R & M with a neighbour w in L but not
+    //in M, are found up front
+    fn construct_g_for_augmenting_path<'b>(
+        &'b self,
+        v: &'b AdmData,
+    ) -> AugmentingPath<AdmAuxiliaryGraph<'b>> {
+        let mut augmenting_path = AugmentingPath::new(
+            v.id,
+            AdmAuxiliaryGraph {
+                graph: self.graph,
+                v,
+            },
+        );
 
-        //Gets edges between vertex u in R & M and vertex w in L but not in M
         for u in v.m_from_r.keys() {
             let u_adm_data = self.adm_data.get(u).unwrap();
             for w in &u_adm_data.n_in_l {
                 if !v.m_from_l.contains_key(w) && !v.n_in_l.contains(w) && v.id != *w {
                     augmenting_path.s.insert(*u);
                     augmenting_path.out.insert(*u, *w);
+                    break;
                 }
             }
         }
         augmenting_path
     }
 
+    //Without the greedy repairs M is not always maximal, an edge between a vertex x in R and a vertex
+    //y in L that are both not in M is an augmenting path of length one which the auxiliary graph
+    //does not contain
+    fn find_unmatched_edge(&self, v: &AdmData) -> Option<MatchingEdges> {
+        for x in &v.n_in_r {
+            if v.m_from_r.contains_key(x) {
+                continue;
+            }
+            for y in &self.adm_data.get(x).unwrap().n_in_l {
+                if v.can_add_vertex_in_l_to_m(y) {
+                    return Some(MatchingEdges {
+                        e_add: [(*y, *x)].into_iter().collect(),
+                        e_remove: VertexMap::default(),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn search(&mut self, v: &AdmData) -> Option<MatchingEdges> {
+        if self.options.skip_n1_repair || self.options.skip_l2_repair {
+            if let Some(edge) = self.find_unmatched_edge(v) {
+                self.stats.unmatched_edges += 1;
+                return Some(edge);
+            }
+        }
+        self.stats.searches += 1;
+        self.stats.aux_vertices += v.m_from_l.len() + v.m_from_r.len();
+        let augmenting_path = self.construct_g_for_augmenting_path(v);
+        //Only the search itself is timed, not building the starts and ends of the auxiliary graph
+        let start = self.options.time_searches.then(Instant::now);
+        let path = augmenting_path.find(self.options.search);
+        if let Some(start) = start {
+            self.search_time += start.elapsed();
+        }
+        if path.is_some() {
+            self.stats.successful_searches += 1;
+        }
+        path
+    }
+
+    //Grows M of v until it is maximum, adding v to candidates, or too big for v to be a candidate.
+    //Without the repairs M can be more than one edge short of a maximum matching
+    fn grow_m(&mut self, v_adm_data: &mut AdmData, p: usize) {
+        while v_adm_data.is_maximal_matching_size_p(p) {
+            match self.search(v_adm_data) {
+                Some(path) => {
+                    v_adm_data.update_m(&path);
+                    self.stats.update_m += 1;
+                }
+                None => {
+                    self.candidates.insert(v_adm_data.id);
+                    break;
+                }
+            }
+        }
+    }
+
+    //Maximum packing of v recomputed as a bipartite matching between its neighbours in R and the
+    //vertices in L they can reach
+    fn exact_packing(&self, v: &AdmData) -> usize {
+        let mut edges = Vec::new();
+        for r in &v.n_in_r {
+            for l in &self.adm_data[r].n_in_l {
+                if !v.n_in_l.contains(l) && *l != v.id {
+                    edges.push((*r, *l));
+                }
+            }
+        }
+        hopcroft_karp::matching(&edges).len()
+    }
+
+    fn compare_with_exact_packing(&mut self, v: &AdmData, p: usize) {
+        let incremental = v.m_from_l.len() + v.n_in_l.len();
+        let exact = self.exact_packing(v) + v.n_in_l.len();
+        if incremental.min(p + 1) != exact.min(p + 1) {
+            self.discrepancies.push(Discrepancy {
+                vertex: v.id,
+                incremental,
+                exact,
+            });
+        }
+    }
+
     fn do_checks(&mut self, p: usize) {
+        self.stats.do_checks += 1;
         let checks = mem::take(&mut self.checks);
         for v in checks {
             let mut v_adm_data = self.adm_data.remove(&v).unwrap();
-            if v_adm_data.is_maximal_matching_size_p(p) {
-                let aug_path = self.construct_g_for_augmenting_path(&mut v_adm_data);
-                let new_path = aug_path.find_augmenting_path();
+            self.grow_m(&mut v_adm_data, p);
+            if self.options.exact_check {
+                self.compare_with_exact_packing(&v_adm_data, p);
+            }
+            self.adm_data.insert(v, v_adm_data);
+        }
+        self.stats.peak_candidates = max(self.stats.peak_candidates, self.candidates.len());
+    }
 
-                match new_path {
-                    Some(path) => {
-                        v_adm_data.update_m(&path);
-                    }
-                    None => {
-                        self.candidates.insert(v);
-                    }
+    //Checks everything the incremental updates rely on from scratch, for each vertex v:
+    //n_in_l and n_in_r split the neighbours of v into L and R, M is mirrored and each edge l-r of M is
+    //a 2-path v-r-l with r in R and l in L not a neighbour of v. For v in L, M is large enough to decide
+    //if v is a candidate: a candidate has value at most p using a maximum packing, any other vertex
+    //has M and n_in_l together bigger than p. This is slow and only meant for catching mistakes in
+    //the incremental logic
+    pub fn check_invariants(&self, p: usize) -> Result<(), String> {
+        let in_l = |u: &Vertex| self.l.contains(u);
+        for (v, v_adm_data) in &self.adm_data {
+            for u in self.graph.neighbours(v) {
+                let expected = in_l(u);
+                if v_adm_data.n_in_l.contains(u) != expected || v_adm_data.n_in_r.contains(u) == expected {
+                    return Err(format!("neighbour {u} of {v} is in the wrong side of n_in_l and n_in_r"));
                 }
             }
-            self.adm_data.insert(v, v_adm_data);
+            if v_adm_data.n_in_l.len() + v_adm_data.n_in_r.len() != self.graph.degree(v) as usize {
+                return Err(format!("n_in_l and n_in_r of {v} have vertices that are not neighbours"));
+            }
+            if v_adm_data.deleted_m {
+                continue;
+            }
+            v_adm_data.check_m_is_mirrored()?;
+            for (l, r) in &v_adm_data.m_from_l {
+                if !v_adm_data.n_in_r.contains(r)
+                    || !self.graph.adjacent(r, l)
+                    || !in_l(l)
+                    || v_adm_data.n_in_l.contains(l)
+                    || l == v
+                {
+                    return Err(format!("edge {l}-{r} in M of {v} is not a 2-path through R to L"));
+                }
+            }
+            if !in_l(v) {
+                continue;
+            }
+            let m_size = v_adm_data.m_from_l.len();
+            let packing = max_packing(self.graph, *v, &in_l);
+            if self.candidates.contains(v) {
+                if v_adm_data.n_in_l.len() + packing > p {
+                    return Err(format!("candidate {v} has value {} above p", v_adm_data.n_in_l.len() + packing));
+                }
+            } else if m_size + v_adm_data.n_in_l.len() <= p {
+                return Err(format!(
+                    "{v} is not a candidate but has M of size {m_size} with a maximum packing of {packing}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn record_witness(&mut self, v: Vertex) {
+        let mut paths: Vec<(Vertex, Vertex)> = self.adm_data[&v]
+            .m_from_r
+            .iter()
+            .map(|(r, l)| (*r, *l))
+            .collect();
+        paths.sort();
+        self.witnesses.insert(v, paths);
+    }
+
+    //The candidates left when all other vertices are in R are never removed, so their M is kept here.
+    //They go at the end of the ordering in any order, so the end in L of a 2-path can come before them
+    pub fn record_candidate_witnesses(&mut self) {
+        for v in self.candidates.clone() {
+            self.record_witness(v);
         }
     }
 
@@ -183,9 +526,17 @@ impl<'a> AdmGraph<'a> {
 
                 self.do_checks(p);
 
+                if self.options.record_witnesses {
+                    self.record_witness(v);
+                }
                 self.adm_data.get_mut(&v).unwrap().delete_m();
 
                 assert_eq!(self.r_count + self.l.len(), self.graph.num_vertices());        
+                if self.options.validate {
+                    if let Err(e) = self.check_invariants(p) {
+                        panic!("Invariant broken after moving {v} to R: {e}");
+                    }
+                }
                 Some(v)
             }
             None => None,
@@ -195,9 +546,10 @@ impl<'a> AdmGraph<'a> {
 
 #[cfg(test)]
 mod test_adm_graph {
-    use crate::admGraph::AdmGraph;
+    use crate::admGraph::{AdmGraph, AdmOptions, Discrepancy};
+    use crate::augmentingPath::AuxiliaryGraph;
     use graphbench::editgraph::EditGraph;
-    use graphbench::graph::{EdgeSet, MutableGraph};
+    use graphbench::graph::{EdgeSet, Graph, MutableGraph};
 
     #[test]
     fn initialise_candidates_should_add_vertices_with_degree_p_or_less_to_candidates() {
@@ -237,6 +589,31 @@ mod test_adm_graph {
         assert_eq!(u_adm_data.m_from_l.len(), 1);
     }
 
+    #[test]
+    fn update_n1_of_v_should_not_add_v_to_m_of_u_if_repair_is_skipped() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 5), (2, 6)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            skip_n1_repair: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+
+        adm_graph.update_n1_of_v(1);
+
+        assert!(adm_graph.adm_data.get(&2).unwrap().m_from_l.is_empty());
+        assert!(adm_graph.adm_data.get(&2).unwrap().n_in_r.contains(&1));
+        assert_eq!(adm_graph.stats.n1_added, 0);
+        assert_eq!(adm_graph.stats.n1_failed, 0);
+        assert_eq!(adm_graph.stats.n1_skipped, 3);
+    }
+
     #[test]
     fn update_n1_of_v_should_move_v_to_m_of_u_if_m_of_u_has_been_deleted() {
         let mut graph = EditGraph::new();
@@ -287,6 +664,40 @@ mod test_adm_graph {
             .unwrap()
             .m_from_l
             .contains_key(&8));
+        assert_eq!(adm_graph.stats.l2_replaced, 1);
+    }
+
+    #[test]
+    fn update_l2_of_v_should_only_remove_v_from_m_of_u_if_repair_is_skipped() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (4, 5), (5, 6), (5, 7), (4, 8)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            skip_l2_repair: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+
+        adm_graph.initialise_candidates(3);
+        let v_adm_data = adm_graph.adm_data.get_mut(&1).unwrap();
+        v_adm_data.move_v_in_l_to_r(&4);
+        v_adm_data.add_edges_to_m(5, 4);
+        let mut u_adm_data = adm_graph.adm_data.remove(&5).unwrap();
+        u_adm_data.move_v_in_l_to_r(&4);
+        u_adm_data.add_edges_to_m(1, 4);
+        adm_graph.adm_data.insert(5, u_adm_data);
+
+        adm_graph.update_l2_of_v(1);
+
+        assert!(adm_graph.adm_data.get(&5).unwrap().m_from_l.is_empty());
+        assert_eq!(adm_graph.stats.l2_replaced, 0);
+        assert_eq!(adm_graph.stats.l2_failed, 0);
+        assert_eq!(adm_graph.stats.l2_skipped, 1);
     }
 
     #[test]
@@ -321,19 +732,26 @@ mod test_adm_graph {
 
         adm_graph.initialise_candidates(3);
         let mut v_adm_data = adm_graph.adm_data.remove(&1).unwrap();
-        [4, 6, 8, 10, 11].map(|x| v_adm_data.move_v_in_l_to_r(&x));
-        [(5, 4), (7, 6), (9, 8)].map(|(l, r)| v_adm_data.add_edges_to_m(l, r));
+        for x in [4, 6, 8, 10, 11] {
+            v_adm_data.move_v_in_l_to_r(&x);
+        }
+        for (l, r) in [(5, 4), (7, 6), (9, 8)] {
+            v_adm_data.add_edges_to_m(l, r);
+        }
 
-        let aug_path = adm_graph.construct_g_for_augmenting_path(&mut v_adm_data);
+        let aug_path = adm_graph.construct_g_for_augmenting_path(&v_adm_data);
 
-        assert_eq!(aug_path.t.len(), 1);
-        assert!(aug_path.t.contains(&9));
         assert_eq!(aug_path.s.len(), 2);
         assert!(aug_path.s.contains(&4));
         assert!(aug_path.s.contains(&6));
-        //Check some of the edges are inserted in the right direction
-        assert!(aug_path.edges.get(&4).unwrap().contains(&5));
-        assert!(aug_path.edges.get(&5).unwrap().contains(&6));
+        //Only 9 has a neighbour in R that is not in M
+        assert!(matches!(aug_path.aux.end(9), Some(10) | Some(11)));
+        assert!(aug_path.aux.end(5).is_none());
+        assert!(aug_path.aux.end(7).is_none());
+        //Check some of the edges are found in the right direction
+        assert_eq!(aug_path.aux.next(4), vec![5]);
+        assert!(aug_path.aux.next(5).contains(&6));
+        assert!(!aug_path.aux.next(5).contains(&4));
     }
 
     #[test]
@@ -355,4 +773,227 @@ mod test_adm_graph {
         assert_eq!(adm_graph.r_count, 1);
         assert_eq!(adm_graph.l.len(), 5);
     }
+
+    #[test]
+    fn remove_v_from_candidates_should_count_steps_in_stats() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 5), (2, 6), (3, 5), (4, 6)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let mut adm_graph = AdmGraph::new(&graph);
+
+        adm_graph.initialise_candidates(2);
+        while adm_graph.remove_v_from_candidates(2).is_some() {}
+
+        let stats = adm_graph.stats;
+        assert_eq!(stats.do_checks, 6);
+        assert!(stats.peak_candidates >= 4);
+        assert!(stats.successful_searches <= stats.searches);
+        assert_eq!(stats.unmatched_edges, 0);
+        assert_eq!(stats.update_m, stats.successful_searches);
+    }
+
+    #[test]
+    fn check_invariants_should_hold_after_each_vertex_moves_to_r() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 5), (2, 6), (3, 5), (4, 6), (5, 6)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            validate: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+
+        adm_graph.initialise_candidates(3);
+        assert!(adm_graph.check_invariants(3).is_ok());
+        while adm_graph.remove_v_from_candidates(3).is_some() {}
+        assert!(adm_graph.check_invariants(3).is_ok());
+    }
+
+    #[test]
+    fn remove_v_from_candidates_should_record_m_as_witnesses() {
+        let mut graph = EditGraph::new();
+        //A 3x3 grid, where the centre can only move to R after one of its neighbours
+        let edges: EdgeSet = [
+            (1, 2),
+            (2, 3),
+            (4, 5),
+            (5, 6),
+            (7, 8),
+            (8, 9),
+            (1, 4),
+            (4, 7),
+            (2, 5),
+            (5, 8),
+            (3, 6),
+            (6, 9),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            record_witnesses: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+
+        adm_graph.initialise_candidates(3);
+        let mut removed = Vec::new();
+        while !adm_graph.is_all_vertices_in_r_or_candidates() {
+            removed.push(adm_graph.remove_v_from_candidates(3).unwrap());
+        }
+        adm_graph.record_candidate_witnesses();
+
+        assert_eq!(adm_graph.witnesses.len(), 9);
+        for (i, v) in removed.iter().enumerate() {
+            for (r, l) in &adm_graph.witnesses[v] {
+                assert!(removed[..i].contains(r));
+                assert!(!removed[..=i].contains(l));
+                assert!(graph.adjacent(v, r) && graph.adjacent(r, l) && !graph.adjacent(v, l));
+            }
+        }
+        assert!(adm_graph.witnesses.values().any(|paths| !paths.is_empty()));
+    }
+
+    #[test]
+    fn check_invariants_should_find_edges_in_m_that_are_not_2_paths() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (2, 4), (3, 5)].iter().cloned().collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let mut adm_graph = AdmGraph::new(&graph);
+        adm_graph.initialise_candidates(2);
+        adm_graph.candidates.remove(&2);
+        adm_graph.l.remove(&2);
+        adm_graph.r_count += 1;
+        for u in [1, 4] {
+            adm_graph.adm_data.get_mut(&u).unwrap().move_v_in_l_to_r(&2);
+        }
+        adm_graph.adm_data.get_mut(&2).unwrap().delete_m();
+        assert!(adm_graph.check_invariants(2).is_ok());
+
+        //5 is not a neighbour of 2
+        adm_graph.adm_data.get_mut(&1).unwrap().add_edges_to_m(5, 2);
+
+        assert!(adm_graph.check_invariants(2).is_err());
+    }
+
+    #[test]
+    fn do_checks_should_not_report_discrepancies_for_incremental_m() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 5), (2, 6), (3, 5), (4, 6), (5, 6)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            exact_check: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+
+        adm_graph.initialise_candidates(2);
+        while adm_graph.remove_v_from_candidates(2).is_some() {}
+
+        assert!(adm_graph.stats.do_checks > 0);
+        assert!(adm_graph.discrepancies.is_empty());
+    }
+
+    #[test]
+    fn do_checks_should_report_vertex_with_m_larger_than_maximum_packing() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (2, 4), (3, 5), (3, 6)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let options = AdmOptions {
+            exact_check: true,
+            ..AdmOptions::default()
+        };
+        let mut adm_graph = AdmGraph::with_options(&graph, options);
+        adm_graph.l.remove(&2);
+        adm_graph.r_count += 1;
+        for u in [1, 4] {
+            adm_graph.adm_data.get_mut(&u).unwrap().move_v_in_l_to_r(&2);
+        }
+        //Only 2-4 is a 2-path through R but M of 1 also has 5 and 6 through 2
+        let v_adm_data = adm_graph.adm_data.get_mut(&1).unwrap();
+        v_adm_data.add_edges_to_m(4, 2);
+        v_adm_data.m_from_l.insert(5, 2);
+        v_adm_data.m_from_l.insert(6, 2);
+        adm_graph.checks.insert(1);
+
+        adm_graph.do_checks(2);
+
+        assert_eq!(
+            adm_graph.discrepancies,
+            vec![Discrepancy {
+                vertex: 1,
+                incremental: 4,
+                exact: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn prune_low_degree_should_move_vertices_with_degree_p_or_less_to_r() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5), (5, 6), (6, 1)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let mut adm_graph = AdmGraph::new(&graph);
+
+        let mut pruned = adm_graph.prune_low_degree(2);
+        pruned.sort();
+
+        assert_eq!(pruned, vec![5, 6]);
+        assert_eq!(adm_graph.r_count, 2);
+        assert_eq!(adm_graph.l.len(), 4);
+        //The only neighbours of 5 and 6 in L are 4 and 1 so they give no 2-paths
+        assert_eq!(adm_graph.adm_data.get(&4).unwrap().m_from_l.len(), 0);
+        assert_eq!(adm_graph.adm_data.get(&1).unwrap().m_from_l.len(), 0);
+        assert!(adm_graph.candidates.is_empty());
+    }
+
+    #[test]
+    fn prune_low_degree_should_add_paths_through_pruned_vertices_to_m() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (2, 3), (1, 4), (1, 5), (3, 6), (3, 7), (4, 5), (6, 7)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let mut adm_graph = AdmGraph::new(&graph);
+
+        let pruned = adm_graph.prune_low_degree(2);
+
+        assert!(pruned.contains(&2));
+        assert!(!pruned.contains(&1));
+        let v_adm_data = adm_graph.adm_data.get(&1).unwrap();
+        assert_eq!(*v_adm_data.m_from_l.get(&3).unwrap(), 2);
+    }
 }
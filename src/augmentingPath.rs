@@ -1,4 +1,18 @@
+use clap::ValueEnum;
 use graphbench::graph::{Vertex, VertexMap, VertexSet};
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchStrategy {
+    //Depth first search from each vertex in s, returning the first augmenting path found
+    #[default]
+    Dfs,
+    //Hopcroft-Karp style phase returning several vertex disjoint shortest augmenting paths. Each start
+    //and end only offers the one vertex outside M given by out and AuxiliaryGraph::end, so a path
+    //whose vertex outside M is taken by another path is dropped even if it has another one
+    Phase,
+}
 
 pub struct MatchingEdges {
     //key vertex v in L, value neighbour of v in M and in R
@@ -6,49 +20,63 @@ pub struct MatchingEdges {
     pub e_remove: VertexMap<Vertex>,
 }
 
-pub struct AugmentingPath {
+//Edges of the auxiliary graph searched for augmenting paths. They are given on demand when the
+//search reaches a vertex so the auxiliary graph never has to be built in full
+pub trait AuxiliaryGraph {
+    //Vertices the search can move to from u
+    fn next(&self, u: Vertex) -> Vec<Vertex>;
+    //If an augmenting path can end at w, the vertex in R and not in M that w gets matched with
+    fn end(&self, w: Vertex) -> Option<Vertex>;
+}
+
+pub struct AugmentingPath<A: AuxiliaryGraph> {
     pub id: Vertex,
     pub s: VertexSet,
-    pub t: VertexSet,
-    pub out: VertexMap<Vertex>,
-    pub edges: VertexMap<VertexSet>,
+    pub out: VertexMap<Vertex>, //key vertex in s, value vertex in L and not in M it gets matched with
+    pub aux: A,
 }
 
-impl AugmentingPath {
-    pub fn new(v: Vertex) -> Self {
+impl<A: AuxiliaryGraph> AugmentingPath<A> {
+    pub fn new(v: Vertex, aux: A) -> Self {
         AugmentingPath {
             id: v,
             s: VertexSet::default(),
-            t: VertexSet::default(),
             out: VertexMap::default(),
-            edges: VertexMap::default(),
+            aux,
         }
     }
 
+    //Whether a path can end is only known once the search reaches a vertex so only s is checked
     pub fn should_do_augmenting_path(&self) -> bool {
-        !self.s.is_empty() && !self.t.is_empty()
+        !self.s.is_empty()
     }
 
-    fn dfs(&self, v: Vertex) -> Option<Vec<Vertex>> {
+    //visited is shared between the searches from each vertex in s, a vertex visited by an earlier
+    //search that did not find a path cannot reach t so it does not need to be explored again.
+    //A path alternates between vertices in R and in L so it can only end after an odd number of steps.
+    //The auxiliary graph of AdmGraph has no other paths to an end, the check only keeps any other
+    //auxiliary graph from giving a path that get_new_matching_edges can not split into pairs
+    fn dfs(&self, v: Vertex, visited: &mut VertexSet) -> Option<Vec<Vertex>> {
         let mut path = vec![v];
-        let mut visited = VertexSet::default();
+        //Neighbours still to explore of each vertex on the path
+        let mut next = vec![self.aux.next(v).into_iter()];
         visited.insert(v);
 
-        'main: while !path.is_empty() {
-            let u = path.last().unwrap();
-            if let Some(u_neighbours) = self.edges.get(u) {
-                for w in u_neighbours {
-                    if !visited.contains(w) {
-                        path.push(*w);
-                        visited.insert(*w);
-                        if self.t.contains(w) {
-                            return Some(path);
-                        }
-                        continue 'main;
+        while let Some(u_neighbours) = next.last_mut() {
+            match u_neighbours.find(|w| !visited.contains(w)) {
+                Some(w) => {
+                    path.push(w);
+                    visited.insert(w);
+                    if path.len() % 2 == 0 && self.aux.end(w).is_some() {
+                        return Some(path);
                     }
+                    next.push(self.aux.next(w).into_iter());
+                }
+                None => {
+                    path.pop();
+                    next.pop();
                 }
-            };
-            path.pop();
+            }
         }
         None
     }
@@ -67,7 +95,7 @@ impl AugmentingPath {
         edges.e_add.insert(*self.out.get(&first).unwrap(), first);
 
         let last = path[path.len() - 1];
-        edges.e_add.insert(last, *self.out.get(&last).unwrap());
+        edges.e_add.insert(last, self.aux.end(last).unwrap());
 
         for c in path[1..path.len() - 1].chunks(2) {
             edges.e_add.insert(c[0], c[1]);
@@ -76,14 +104,125 @@ impl AugmentingPath {
         edges
     }
 
+    //Distance from s of every vertex reached by a breadth first search that stops at the first layer
+    //containing a vertex in t, together with the length of the shortest augmenting path. As in dfs
+    //only vertices at an odd distance can end a path
+    fn bfs_layers(&self) -> Option<(VertexMap<usize>, usize)> {
+        let mut dist = VertexMap::default();
+        let mut queue = VecDeque::new();
+        for v in &self.s {
+            dist.insert(*v, 0);
+            queue.push_back(*v);
+        }
+
+        let mut target = None;
+        while let Some(u) = queue.pop_front() {
+            let d = dist[&u];
+            if target.is_some_and(|t| d >= t) {
+                break;
+            }
+            for w in self.aux.next(u) {
+                if let Entry::Vacant(entry) = dist.entry(w) {
+                    entry.insert(d + 1);
+                    if target.is_none() && (d + 1) % 2 == 1 && self.aux.end(w).is_some() {
+                        target = Some(d + 1);
+                    }
+                    queue.push_back(w);
+                }
+            }
+        }
+        target.map(|t| (dist, t))
+    }
+
+    //Follows the bfs layers from v to a vertex in t, vertices that are visited are not visited
+    //again in the same phase as they are either on a path or cannot reach t
+    fn layered_dfs(
+        &self,
+        v: Vertex,
+        dist: &VertexMap<usize>,
+        target: usize,
+        used: &mut VertexSet,
+        path: &mut Vec<Vertex>,
+    ) -> bool {
+        path.push(v);
+        used.insert(v);
+        let d = dist[&v];
+        if d == target {
+            match self.aux.end(v) {
+                Some(end) if !used.contains(&end) => {
+                    used.insert(end);
+                    return true;
+                }
+                _ => {}
+            }
+        } else {
+            for w in self.aux.next(v) {
+                if dist.get(&w) == Some(&(d + 1))
+                    && !used.contains(&w)
+                    && self.layered_dfs(w, dist, target, used, path)
+                {
+                    return true;
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+
+    //Finds several vertex disjoint shortest augmenting paths at once. The vertices outside M at the
+    //ends of the paths are also kept distinct so the paths can all be added to M together. The set of
+    //paths is not always maximal as only the one vertex outside M of each start and end is tried
+    pub fn find_augmenting_paths(&self) -> Option<MatchingEdges> {
+        if !self.should_do_augmenting_path() {
+            return None;
+        }
+        let (dist, target) = self.bfs_layers()?;
+
+        let mut edges = MatchingEdges {
+            e_add: VertexMap::default(),
+            e_remove: VertexMap::default(),
+        };
+        let mut used = VertexSet::default();
+        for v in &self.s {
+            let start = self.out[v];
+            if used.contains(v) || used.contains(&start) {
+                continue;
+            }
+            let mut path = Vec::new();
+            if self.layered_dfs(*v, &dist, target, &mut used, &mut path) {
+                used.insert(start);
+                let m = self.get_new_matching_edges(path);
+                edges.e_add.extend(m.e_add);
+                edges.e_remove.extend(m.e_remove);
+            }
+        }
+
+        if edges.e_add.is_empty() {
+            None
+        } else {
+            Some(edges)
+        }
+    }
+
+    pub fn find(&self, strategy: SearchStrategy) -> Option<MatchingEdges> {
+        match strategy {
+            SearchStrategy::Dfs => self.find_augmenting_path(),
+            SearchStrategy::Phase => self.find_augmenting_paths(),
+        }
+    }
+
     pub fn find_augmenting_path(&self) -> Option<MatchingEdges> {
-        //If there is no start an end points there is no need doing augmenting path
+        //If there is no start points there is no need doing augmenting path
         if !self.should_do_augmenting_path() {
             return None;
         }
 
+        let mut visited = VertexSet::default();
         for v in &self.s {
-            match self.dfs(*v) {
+            if visited.contains(v) {
+                continue;
+            }
+            match self.dfs(*v, &mut visited) {
                 None => {}
                 Some(path) => {
                     let m = self.get_new_matching_edges(path);
@@ -97,73 +236,230 @@ impl AugmentingPath {
 
 #[cfg(test)]
 mod test_augmenting_path {
-    use crate::augmentingPath::AugmentingPath;
-    use graphbench::graph::Vertex;
+    use crate::augmentingPath::{AugmentingPath, AuxiliaryGraph, MatchingEdges};
+    use graphbench::graph::{Vertex, VertexMap, VertexSet};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
-    #[test]
-    fn test_should_do_augmenting_path_should_return_false_if_s_and_t_is_empty() {
-        let aug_path = AugmentingPath::new(1);
-        assert!(!aug_path.should_do_augmenting_path());
+    //Auxiliary graph given by its edges and the vertices in t with the vertex each gets matched with
+    #[derive(Default)]
+    struct TestGraph {
+        edges: VertexMap<VertexSet>,
+        t: VertexMap<Vertex>,
     }
 
-    #[test]
-    fn test_should_do_augmenting_path_should_return_false_if_s_is_empty() {
-        let mut aug_path = AugmentingPath::new(1);
-        aug_path.t.insert(2);
-        assert!(!aug_path.should_do_augmenting_path());
+    impl AuxiliaryGraph for TestGraph {
+        fn next(&self, u: Vertex) -> Vec<Vertex> {
+            self.edges
+                .get(&u)
+                .map_or(vec![], |u_neighbours| u_neighbours.iter().copied().collect())
+        }
+
+        fn end(&self, w: Vertex) -> Option<Vertex> {
+            self.t.get(&w).copied()
+        }
+    }
+
+    fn aug_path(
+        s: &[(Vertex, Vertex)],
+        t: &[(Vertex, Vertex)],
+        edges: &[(Vertex, Vertex)],
+    ) -> AugmentingPath<TestGraph> {
+        let mut aug_path = AugmentingPath::new(1, TestGraph::default());
+        for (v, out) in s {
+            aug_path.s.insert(*v);
+            aug_path.out.insert(*v, *out);
+        }
+        aug_path.aux.t.extend(t.iter().copied());
+        for (v, u) in edges {
+            aug_path.aux.edges.entry(*v).or_default().insert(*u);
+        }
+        aug_path
     }
 
     #[test]
-    fn test_should_do_augmenting_path_should_return_false_if_t_is_empty() {
-        let mut aug_path = AugmentingPath::new(1);
-        aug_path.s.insert(2);
+    fn test_should_do_augmenting_path_should_return_false_if_s_is_empty() {
+        let aug_path = aug_path(&[], &[(3, 12)], &[]);
         assert!(!aug_path.should_do_augmenting_path());
     }
 
     #[test]
-    fn test_should_do_augmenting_path_should_return_true_if_s_and_t_is_not_empty() {
-        let mut aug_path = AugmentingPath::new(1);
-        aug_path.s.insert(2);
-        aug_path.t.insert(3);
+    fn test_should_do_augmenting_path_should_return_true_if_s_is_not_empty() {
+        let aug_path = aug_path(&[(2, 10)], &[], &[]);
         assert!(aug_path.should_do_augmenting_path());
     }
 
     #[test]
     fn test_get_new_matching_edges_should_return_edges_in_matching_and_edges_to_remove_from_matching(
     ) {
-        let mut aug_path = AugmentingPath::new(1);
-        aug_path.s.extend([2, 6]);
-        aug_path.t.extend([7, 9]);
-        let mut path: Vec<Vertex> = Vec::new();
-        [2, 3, 4, 5, 6, 7, 8, 9].map(|x| path.push(x));
-        aug_path.out.insert(2, 10);
-        aug_path.out.insert(6, 11);
-        aug_path.out.insert(7, 12);
-        aug_path.out.insert(9, 13);
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(7, 12), (9, 13)], &[]);
+        let path: Vec<Vertex> = vec![2, 3, 4, 5, 6, 7, 8, 9];
 
         let edges = aug_path.get_new_matching_edges(path);
 
         assert!(edges.e_add.contains_key(&10));
+        assert!(edges.e_add.contains_key(&9));
         assert!(edges.e_remove.contains_key(&3));
     }
 
     #[test]
     fn test_find_augmenting_path_should_return_edges_in_matching() {
-        let mut aug_path = AugmentingPath::new(1);
-        aug_path.s.extend([2, 6]);
-        aug_path.t.extend([7, 9]);
-        aug_path.out.insert(2, 10);
-        aug_path.out.insert(6, 11);
-        aug_path.out.insert(7, 12);
-        aug_path.out.insert(9, 13);
-
         let edges = [(2, 3), (3, 4), (4, 5), (5, 6), (6, 7), (7, 8), (8, 9)];
-        for (v, u) in edges {
-            aug_path.edges.entry(v).or_default().insert(u);
-        }
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(7, 12), (9, 13)], &edges);
 
         let path = aug_path.find_augmenting_path();
 
         assert!(path.is_some());
     }
+
+    #[test]
+    fn test_find_augmenting_path_should_not_end_at_vertices_outside_t() {
+        let edges = [(2, 3), (3, 4), (4, 5)];
+        let aug_path = aug_path(&[(2, 10)], &[(5, 12)], &edges);
+
+        let edges = aug_path.find_augmenting_path().unwrap();
+
+        assert_eq!(edges.e_remove.len(), 2);
+        assert_eq!(edges.e_add.get(&5), Some(&12));
+    }
+
+    #[test]
+    fn test_find_augmenting_path_should_only_end_after_odd_number_of_steps() {
+        let even = aug_path(&[(2, 10)], &[(4, 12)], &[(2, 3), (3, 4)]);
+        let odd = aug_path(&[(2, 10)], &[(4, 12), (5, 13)], &[(2, 3), (3, 4), (4, 5)]);
+
+        assert!(even.find_augmenting_path().is_none());
+        assert!(even.find_augmenting_paths().is_none());
+        assert_eq!(odd.find_augmenting_path().unwrap().e_remove.len(), 2);
+        assert_eq!(odd.find_augmenting_paths().unwrap().e_remove.len(), 2);
+    }
+
+    #[test]
+    fn test_find_augmenting_paths_should_return_all_vertex_disjoint_paths() {
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(3, 12), (7, 13)], &[(2, 3), (6, 7)]);
+
+        let edges = aug_path.find_augmenting_paths().unwrap();
+
+        assert_eq!(edges.e_remove.len(), 2);
+        assert_eq!(edges.e_add.len(), 4);
+        assert_eq!(*edges.e_add.get(&10).unwrap(), 2);
+        assert_eq!(*edges.e_add.get(&11).unwrap(), 6);
+        assert_eq!(*edges.e_add.get(&3).unwrap(), 12);
+        assert_eq!(*edges.e_add.get(&7).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_find_augmenting_paths_should_not_reuse_end_vertices_outside_m() {
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(3, 12), (7, 12)], &[(2, 3), (6, 7)]);
+
+        let edges = aug_path.find_augmenting_paths().unwrap();
+
+        assert_eq!(edges.e_remove.len(), 1);
+        assert_eq!(edges.e_add.len(), 2);
+    }
+
+    #[test]
+    fn test_find_augmenting_paths_should_only_return_shortest_paths() {
+        let edges = [(2, 3), (3, 4), (4, 5), (6, 7)];
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(5, 12), (7, 13)], &edges);
+
+        let edges = aug_path.find_augmenting_paths().unwrap();
+
+        assert_eq!(edges.e_remove.len(), 1);
+        assert!(edges.e_remove.contains_key(&7));
+    }
+
+    #[test]
+    fn test_find_augmenting_paths_should_return_none_if_t_cannot_be_reached() {
+        let aug_path = aug_path(&[(2, 10)], &[(5, 12)], &[(2, 3)]);
+
+        assert!(aug_path.find_augmenting_paths().is_none());
+    }
+
+    #[test]
+    fn test_find_augmenting_path_should_find_path_from_later_start_through_shared_vertices() {
+        //3 and 4 are a dead end from both 2 and 6, only 6 can reach 5
+        let edges = [(2, 3), (3, 4), (6, 3), (6, 7), (7, 8), (8, 5)];
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(5, 12)], &edges);
+
+        let edges = aug_path.find_augmenting_path().unwrap();
+
+        assert_eq!(edges.e_remove.get(&7), Some(&6));
+        assert_eq!(edges.e_remove.get(&5), Some(&8));
+    }
+
+    #[test]
+    fn test_find_augmenting_path_should_return_none_if_no_start_reaches_t() {
+        let edges = [(2, 3), (6, 3), (3, 4), (4, 2)];
+        let aug_path = aug_path(&[(2, 10), (6, 11)], &[(5, 12)], &edges);
+
+        assert!(aug_path.find_augmenting_path().is_none());
+    }
+
+    //Applies the edges to M, given by its edges from L to R, and checks M stays a matching of edges
+    //the search could have used, grown by one edge for each path
+    fn assert_consistent(aug_path: &AugmentingPath<TestGraph>, m: &VertexMap<Vertex>, edges: &MatchingEdges) {
+        let mut new_m = m.clone();
+        for (l, r) in &edges.e_remove {
+            assert_eq!(m.get(l), Some(r), "removed {l}-{r} is not in M");
+            new_m.remove(l);
+        }
+        for (l, r) in &edges.e_add {
+            let is_start = aug_path.out.get(r) == Some(l);
+            let is_end = aug_path.aux.end(*l) == Some(*r);
+            let is_aux_edge = aug_path.aux.next(*l).contains(r);
+            assert!(is_start || is_end || is_aux_edge, "added {l}-{r} is not an edge");
+            assert!(new_m.insert(*l, *r).is_none(), "{l} is matched twice");
+        }
+        let matched_r: VertexSet = new_m.values().copied().collect();
+        assert_eq!(matched_r.len(), new_m.len(), "a vertex in R is matched twice");
+        assert!(edges.e_remove.len() < edges.e_add.len());
+        let paths = edges.e_add.iter().filter(|(l, r)| aug_path.out.get(r) == Some(l)).count();
+        assert_eq!(new_m.len(), m.len() + paths);
+    }
+
+    //Random auxiliary graphs shaped like the ones of AdmGraph: vertices 100.. in R & M are matched with
+    //200.. in L & M, starts in s are matched with 300.. in L and ends in t with 400.. in R. The number
+    //of graphs tried can be raised with FUZZ_ITERATIONS
+    #[test]
+    fn fuzz_find_augmenting_path_should_return_consistent_matching_edges() {
+        let iterations = std::env::var("FUZZ_ITERATIONS").map_or(2000, |n| n.parse().unwrap());
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..iterations {
+            let matched = rng.gen_range(1..10);
+            let (outs, ends) = (rng.gen_range(1..4), rng.gen_range(1..4));
+            let density = rng.gen_range(0.0..0.6);
+            let mut s = Vec::new();
+            let mut t = Vec::new();
+            let mut edges = Vec::new();
+            let mut m = VertexMap::default();
+            for i in 0..matched {
+                let (r, l) = (100 + i, 200 + i);
+                m.insert(l, r);
+                edges.push((r, l));
+                for j in 0..matched {
+                    if rng.gen_bool(density) {
+                        edges.push((l, 100 + j));
+                    }
+                }
+                if rng.gen_bool(0.4) {
+                    s.push((r, 300 + rng.gen_range(0..outs)));
+                }
+                if rng.gen_bool(0.3) {
+                    t.push((l, 400 + rng.gen_range(0..ends)));
+                }
+            }
+            let aug_path = aug_path(&s, &t, &edges);
+
+            let path = aug_path.find_augmenting_path();
+            let paths = aug_path.find_augmenting_paths();
+
+            assert_eq!(path.is_some(), paths.is_some(), "s {s:?} t {t:?} edges {edges:?}");
+            if let (Some(path), Some(paths)) = (path, paths) {
+                assert_consistent(&aug_path, &m, &path);
+                assert_eq!(path.e_add.len(), path.e_remove.len() + 1);
+                assert_consistent(&aug_path, &m, &paths);
+            }
+        }
+    }
 }
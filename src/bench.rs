@@ -0,0 +1,208 @@
+use crate::admGraph::{AdmGraph, AdmOptions};
+use crate::augmentingPath::SearchStrategy;
+use crate::generators::{barabasi_albert, erdos_renyi, grid, random_geometric};
+use crate::lowerBound::degeneracy;
+use crate::{compute_ordering, compute_ordering_with_options, run_elimination};
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::Graph;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+//Time of each phase of one elimination, search is the part of eliminate spent in the calls to
+//AugmentingPath::find. compute_ordering is timed in a run of its own so it is not slowed down by
+//timing the searches
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhaseTimes {
+    pub construct: Duration, //AdmGraph::with_options
+    pub eliminate: Duration, //initialising the candidates and moving vertices to R
+    pub search: Duration,
+    pub compute_ordering: Duration,
+}
+
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Summary {
+    fn new(mut times: Vec<Duration>) -> Self {
+        times.sort();
+        Summary {
+            min: times[0],
+            median: times[times.len() / 2],
+            mean: times.iter().sum::<Duration>() / times.len() as u32,
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub name: String,
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    pub p: usize,
+    pub is_p: bool,
+    pub strategy: SearchStrategy,
+    pub construct: Summary,
+    pub eliminate: Summary,
+    pub search: Summary,
+    pub compute_ordering: Summary,
+}
+
+pub fn time_elimination(graph: &EditGraph, p: usize, options: &AdmOptions) -> (bool, PhaseTimes) {
+    let options = AdmOptions {
+        time_searches: true,
+        ..*options
+    };
+    let start = Instant::now();
+    let mut adm_graph = AdmGraph::with_options(graph, options);
+    let construct = start.elapsed();
+    let start = Instant::now();
+    let is_p = run_elimination(&mut adm_graph, p, graph, false, &options).is_some();
+    let eliminate = start.elapsed();
+    let search = adm_graph.search_time;
+    drop(adm_graph);
+
+    let options = AdmOptions {
+        time_searches: false,
+        ..options
+    };
+    let start = Instant::now();
+    compute_ordering_with_options(p, graph, false, &options);
+    let times = PhaseTimes {
+        construct,
+        eliminate,
+        search,
+        compute_ordering: start.elapsed(),
+    };
+    (is_p, times)
+}
+
+//Smallest p of graph, starting from the degeneracy as it is a lower bound
+pub fn smallest_p(graph: &EditGraph) -> usize {
+    let mut p = degeneracy(graph);
+    while compute_ordering(p, graph, false).is_none() {
+        p += 1;
+    }
+    p
+}
+
+//Runs the elimination for p once to warm up and then repeat times
+pub fn bench_graph(name: &str, graph: &EditGraph, p: usize, repeat: usize, options: &AdmOptions) -> BenchResult {
+    let (is_p, _) = time_elimination(graph, p, options);
+    let runs: Vec<PhaseTimes> = (0..repeat.max(1)).map(|_| time_elimination(graph, p, options).1).collect();
+    BenchResult {
+        name: name.to_string(),
+        num_vertices: graph.num_vertices(),
+        num_edges: graph.num_edges(),
+        p,
+        is_p,
+        strategy: options.search,
+        construct: Summary::new(runs.iter().map(|t| t.construct).collect()),
+        eliminate: Summary::new(runs.iter().map(|t| t.eliminate).collect()),
+        search: Summary::new(runs.iter().map(|t| t.search).collect()),
+        compute_ordering: Summary::new(runs.iter().map(|t| t.compute_ordering).collect()),
+    }
+}
+
+//Benchmarks the smallest p of graph, where an ordering is found, and the p below it, where the
+//elimination stalls, with each search strategy so they can be compared. The search of options is
+//replaced by each strategy in turn
+pub fn bench_smallest_p(name: &str, graph: &EditGraph, repeat: usize, options: &AdmOptions) -> Vec<BenchResult> {
+    let p = smallest_p(graph);
+    let mut results = Vec::new();
+    for search in [SearchStrategy::Dfs, SearchStrategy::Phase] {
+        let options = AdmOptions { search, ..*options };
+        results.push(bench_graph(name, graph, p, repeat, &options));
+        if p > 0 {
+            results.push(bench_graph(name, graph, p - 1, repeat, &options));
+        }
+    }
+    results
+}
+
+//Fixed graphs of each generated family with a few thousand vertices
+pub fn generated_graphs() -> Vec<(String, EditGraph)> {
+    vec![
+        ("grid-50x50".to_string(), grid(50, 50)),
+        ("geometric-2000".to_string(), random_geometric(2000, 0.035, 1)),
+        ("barabasi-albert-2000-3".to_string(), barabasi_albert(2000, 3, 1)),
+        ("erdos-renyi-2000".to_string(), erdos_renyi(2000, 0.003, 1)),
+    ]
+}
+
+fn write_summary(text: &mut String, phase: &str, summary: &Summary) {
+    writeln!(
+        text,
+        "    {} {:.3} ms min, {:.3} ms median, {:.3} ms mean",
+        phase,
+        summary.min.as_secs_f64() * 1000.0,
+        summary.median.as_secs_f64() * 1000.0,
+        summary.mean.as_secs_f64() * 1000.0
+    )
+    .unwrap();
+}
+
+pub fn to_text(results: &[BenchResult]) -> String {
+    let mut text = String::new();
+    for result in results {
+        writeln!(
+            text,
+            "{} with {} vertices and {} edges, p = {} is p: {}, {:?} search",
+            result.name, result.num_vertices, result.num_edges, result.p, result.is_p, result.strategy
+        )
+        .unwrap();
+        let search = match result.strategy {
+            SearchStrategy::Dfs => "AugmentingPath::find_augmenting_path",
+            SearchStrategy::Phase => "AugmentingPath::find_augmenting_paths",
+        };
+        write_summary(&mut text, "AdmGraph::with_options", &result.construct);
+        write_summary(&mut text, "run_elimination", &result.eliminate);
+        write_summary(&mut text, search, &result.search);
+        write_summary(&mut text, "compute_ordering", &result.compute_ordering);
+    }
+    //Median of compute_ordering with the phase search against the dfs search for the same graph and p
+    for dfs in results.iter().filter(|r| r.strategy == SearchStrategy::Dfs) {
+        let phase = results
+            .iter()
+            .find(|r| r.strategy == SearchStrategy::Phase && r.name == dfs.name && r.p == dfs.p);
+        if let Some(phase) = phase {
+            writeln!(
+                text,
+                "{} p = {}: compute_ordering with the phase search takes {:.2} times the dfs search",
+                dfs.name,
+                dfs.p,
+                phase.compute_ordering.median.as_secs_f64() / dfs.compute_ordering.median.as_secs_f64()
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod test_bench {
+    use crate::admGraph::AdmOptions;
+    use crate::augmentingPath::SearchStrategy;
+    use crate::bench::{bench_smallest_p, smallest_p, to_text};
+    use crate::generators::grid;
+
+    #[test]
+    fn bench_smallest_p_should_time_p_and_p_below() {
+        let graph = grid(4, 4);
+
+        let results = bench_smallest_p("grid", &graph, 2, &AdmOptions::default());
+
+        assert_eq!(smallest_p(&graph), 3);
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_p && !results[1].is_p);
+        assert!(results[2].is_p && !results[3].is_p);
+        assert_eq!(results[2].strategy, SearchStrategy::Phase);
+        assert!(results[1].search.min <= results[1].eliminate.min);
+        let text = to_text(&results);
+        assert!(text.starts_with("grid with 16 vertices and 24 edges, p = 3 is p: true, Dfs search\n"));
+        assert!(text.contains("    AugmentingPath::find_augmenting_path "));
+        assert!(text.contains("    AugmentingPath::find_augmenting_paths "));
+        assert!(text.contains("grid p = 2: compute_ordering with the phase search takes "));
+    }
+}
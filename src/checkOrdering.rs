@@ -0,0 +1,120 @@
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+
+//Position of each vertex in an ordering given in the order vertices are removed by compute_ordering
+pub fn positions(order: &[Vertex]) -> VertexMap<usize> {
+    order.iter().enumerate().map(|(i, v)| (*v, i)).collect()
+}
+
+//Checks the value of v when it is removed, where R is the set of vertices before v in the
+//ordering and L the vertices after v. This is the number of neighbours of v in L plus the
+//maximum number of 2-paths v-r-l with r in R and l in L not a neighbour of v, where no two
+//paths share r or l. Computed from scratch as a bipartite matching between the r and l vertices
+pub fn vertex_p(graph: &EditGraph, v: Vertex, positions: &VertexMap<usize>) -> usize {
+    let v_position = positions[&v];
+    let in_l = |u: &Vertex| positions[u] > v_position;
+
+    let n_in_l = graph.neighbours(&v).filter(|u| in_l(u)).count();
+    n_in_l + max_packing(graph, v, &in_l)
+}
+
+//Maximum number of 2-paths v-r-l with r in R and l in L not a neighbour of v, where no two paths
+//share r or l and in_l tells if a vertex is in L
+pub fn max_packing<F: Fn(&Vertex) -> bool>(graph: &EditGraph, v: Vertex, in_l: &F) -> usize {
+    let n_in_r: Vec<Vertex> = graph.neighbours(&v).filter(|u| !in_l(u)).copied().collect();
+
+    let mut matched = VertexMap::default(); //key vertex in L, value vertex in R
+    let mut matching_size = 0;
+    for r in n_in_r {
+        let mut visited = VertexSet::default();
+        if augment(graph, v, r, in_l, &mut visited, &mut matched) {
+            matching_size += 1;
+        }
+    }
+    matching_size
+}
+
+fn augment<F: Fn(&Vertex) -> bool>(
+    graph: &EditGraph,
+    v: Vertex,
+    r: Vertex,
+    in_l: &F,
+    visited: &mut VertexSet,
+    matched: &mut VertexMap<Vertex>,
+) -> bool {
+    for l in graph.neighbours(&r) {
+        if !in_l(l) || *l == v || graph.adjacent(&v, l) || !visited.insert(*l) {
+            continue;
+        }
+        let can_match = match matched.get(l) {
+            None => true,
+            Some(&other_r) => augment(graph, v, other_r, in_l, visited, matched),
+        };
+        if can_match {
+            matched.insert(*l, r);
+            return true;
+        }
+    }
+    false
+}
+
+//The smallest p for which order is a valid ordering, order must contain every vertex of graph
+pub fn ordering_p(graph: &EditGraph, order: &[Vertex]) -> usize {
+    assert_eq!(order.len(), graph.num_vertices());
+    let positions = positions(order);
+    order
+        .iter()
+        .map(|v| vertex_p(graph, *v, &positions))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test_check_ordering {
+    use crate::checkOrdering::{ordering_p, positions, vertex_p};
+    use graphbench::editgraph::EditGraph;
+    use graphbench::graph::{EdgeSet, MutableGraph};
+
+    #[test]
+    fn vertex_p_should_count_neighbours_in_l_and_disjoint_paths_through_r() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 5), (3, 5), (4, 6), (4, 7)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        //2, 3 and 4 are in R, 5, 6 and 7 are in L
+        let positions = positions(&[2, 3, 4, 1, 5, 6, 7]);
+
+        //Paths 1-2-5 and 1-3-5 share 5 so only one of them and one path through 4 count
+        assert_eq!(vertex_p(&graph, 1, &positions), 2);
+    }
+
+    #[test]
+    fn vertex_p_should_not_count_paths_to_neighbours_in_l() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (2, 3)].iter().cloned().collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        let positions = positions(&[2, 1, 3]);
+
+        assert_eq!(vertex_p(&graph, 1, &positions), 1);
+    }
+
+    #[test]
+    fn ordering_p_should_return_max_over_vertices() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+
+        assert_eq!(ordering_p(&graph, &[1, 2, 3, 4]), 3);
+    }
+}
@@ -0,0 +1,85 @@
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap};
+use std::collections::HashSet;
+
+//Colours the vertices in the reverse of the order they are removed by compute_ordering, giving each
+//vertex the smallest colour not used by a vertex at distance at most 2 that is already coloured
+pub fn distance_2_colouring(graph: &EditGraph, order: &[Vertex]) -> VertexMap<usize> {
+    let mut colouring = VertexMap::default();
+    for v in order.iter().rev() {
+        let mut used = HashSet::new();
+        for u in graph.neighbours(v) {
+            used.extend(colouring.get(u).copied());
+            for w in graph.neighbours(u) {
+                used.extend(colouring.get(w).copied());
+            }
+        }
+        let colour = (0..).find(|c| !used.contains(c)).unwrap();
+        colouring.insert(*v, colour);
+    }
+    colouring
+}
+
+//Upper bound on the colours used by distance_2_colouring for an ordering with value at most p. When v
+//is coloured, the coloured vertices within distance 2 are in L: its k <= p neighbours in L, at most
+//max_degree - 1 further vertices through each of those, and the vertices reached by 2-paths through R.
+//A maximum packing of those 2-paths has at most p - k paths so by Konig's theorem at most p - k vertices
+//cover all of them, each covering at most max_degree - 1 paths. So at most p * max_degree vertices are
+//coloured before v within distance 2
+pub fn colour_bound(p: usize, max_degree: usize) -> usize {
+    p * max_degree + 1
+}
+
+pub fn num_colours(colouring: &VertexMap<usize>) -> usize {
+    colouring.values().max().map_or(0, |c| c + 1)
+}
+
+//Checks that every vertex is coloured and no two vertices within distance 2 have the same colour
+pub fn is_distance_2_colouring(graph: &EditGraph, colouring: &VertexMap<usize>) -> bool {
+    graph.vertices().all(|v| match colouring.get(v) {
+        None => false,
+        Some(colour) => graph.neighbours(v).all(|u| {
+            colouring.get(u) != Some(colour)
+                && graph
+                    .neighbours(u)
+                    .all(|w| w == v || colouring.get(w) != Some(colour))
+        }),
+    })
+}
+
+#[cfg(test)]
+mod test_colouring {
+    use crate::colouring::{colour_bound, distance_2_colouring, is_distance_2_colouring, num_colours};
+    use crate::compute_ordering;
+    use crate::generators::graph_from_edges;
+
+    #[test]
+    fn distance_2_colouring_should_give_vertices_within_distance_2_different_colours() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 1), (1, 6), (6, 7)]);
+        let order = compute_ordering(2, &graph, true).unwrap();
+
+        let colouring = distance_2_colouring(&graph, &order);
+
+        assert!(is_distance_2_colouring(&graph, &colouring));
+        assert!(num_colours(&colouring) <= colour_bound(2, 3));
+    }
+
+    #[test]
+    fn distance_2_colouring_should_use_degree_plus_one_colours_for_star() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        let order = compute_ordering(1, &graph, true).unwrap();
+
+        let colouring = distance_2_colouring(&graph, &order);
+
+        assert_eq!(num_colours(&colouring), 5);
+        assert_eq!(colour_bound(1, 4), 5);
+    }
+
+    #[test]
+    fn is_distance_2_colouring_should_find_vertices_at_distance_2_with_same_colour() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3)]);
+        let colouring = [(1, 0), (2, 1), (3, 0)].iter().cloned().collect();
+
+        assert!(!is_distance_2_colouring(&graph, &colouring));
+    }
+}
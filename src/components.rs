@@ -0,0 +1,156 @@
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, MutableGraph, Vertex, VertexSet};
+use std::collections::VecDeque;
+
+//The component attaining the maximum p, identified by its smallest vertex
+pub struct MaxComponent {
+    pub vertex: Vertex,
+    pub num_vertices: usize,
+    pub num_edges: usize,
+}
+
+//Number of components, number that needed the p search and the component attaining the max
+pub type ComponentCounts = (usize, usize, Option<MaxComponent>);
+
+pub fn connected_components(graph: &EditGraph) -> Vec<VertexSet> {
+    let mut components = Vec::new();
+    let mut seen = VertexSet::default();
+
+    for v in graph.vertices() {
+        if seen.contains(v) {
+            continue;
+        }
+        let mut component = VertexSet::default();
+        let mut queue = VecDeque::from([*v]);
+        seen.insert(*v);
+        while let Some(u) = queue.pop_front() {
+            component.insert(u);
+            for w in graph.neighbours(&u) {
+                if seen.insert(*w) {
+                    queue.push_back(*w);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+//Subgraph induced by vertices, keeping the vertex ids of graph
+pub fn induced_subgraph(graph: &EditGraph, vertices: &VertexSet) -> EditGraph {
+    let mut subgraph = EditGraph::new();
+    for v in vertices {
+        subgraph.add_vertex(v);
+        for u in graph.neighbours(v) {
+            if vertices.contains(u) {
+                subgraph.add_edge(v, u);
+            }
+        }
+    }
+    subgraph
+}
+
+//For a connected graph whose 2-admissibility is known (a single vertex, a tree or a cycle)
+//returns p and an ordering in the order vertices are removed by compute_ordering
+pub fn known_ordering(graph: &EditGraph) -> Option<(i32, Vec<Vertex>)> {
+    let n = graph.num_vertices();
+    let v = *graph.vertices().next()?;
+
+    if graph.num_edges() + 1 == n {
+        //Removing vertices in reverse bfs order means every vertex only has its parent in L
+        let mut order = Vec::with_capacity(n);
+        let mut seen: VertexSet = [v].into_iter().collect();
+        let mut queue = VecDeque::from([v]);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for w in graph.neighbours(&u) {
+                if seen.insert(*w) {
+                    queue.push_back(*w);
+                }
+            }
+        }
+        order.reverse();
+        let p = if n == 1 { 0 } else { 1 };
+        return Some((p, order));
+    }
+
+    //Every vertex of a cycle has two neighbours so any ordering gives p = 2
+    if graph.num_edges() == n && graph.vertices().all(|u| graph.degree(u) == 2) {
+        return Some((2, graph.vertices().copied().collect()));
+    }
+    None
+}
+
+#[cfg(test)]
+mod test_components {
+    use crate::components::{connected_components, induced_subgraph, known_ordering};
+    use crate::generators::graph_from_edges;
+    use graphbench::editgraph::EditGraph;
+    use graphbench::graph::{Graph, MutableGraph, VertexSet};
+
+    #[test]
+    fn connected_components_should_split_graph_into_components() {
+        let mut graph = graph_from_edges(&[(1, 2), (2, 3), (4, 5)]);
+        graph.add_vertex(&6);
+
+        let mut components = connected_components(&graph);
+        components.sort_by_key(|c| c.len());
+
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0], [6].iter().cloned().collect());
+        assert_eq!(components[1], [4, 5].iter().cloned().collect());
+        assert_eq!(components[2], [1, 2, 3].iter().cloned().collect());
+    }
+
+    #[test]
+    fn induced_subgraph_should_only_keep_edges_between_vertices() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let vertices: VertexSet = [1, 2, 4].iter().cloned().collect();
+
+        let subgraph = induced_subgraph(&graph, &vertices);
+
+        assert_eq!(subgraph.num_vertices(), 3);
+        assert_eq!(subgraph.num_edges(), 1);
+        assert!(subgraph.adjacent(&1, &2));
+    }
+
+    #[test]
+    fn known_ordering_should_return_1_for_trees_with_leaves_first() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (2, 5), (2, 6)]);
+
+        let (p, order) = known_ordering(&graph).unwrap();
+
+        assert_eq!(p, 1);
+        assert_eq!(order.len(), 6);
+        //Each vertex has at most one neighbour later in the ordering
+        for (i, v) in order.iter().enumerate() {
+            let later = graph.neighbours(v).filter(|u| order[i..].contains(u)).count();
+            assert!(later <= 1);
+        }
+    }
+
+    #[test]
+    fn known_ordering_should_return_0_for_single_vertex() {
+        let mut graph = EditGraph::new();
+        graph.add_vertex(&1);
+
+        assert_eq!(known_ordering(&graph), Some((0, vec![1])));
+    }
+
+    #[test]
+    fn known_ordering_should_return_2_for_cycles() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1)]);
+
+        let (p, order) = known_ordering(&graph).unwrap();
+
+        assert_eq!(p, 2);
+        assert_eq!(order.len(), 4);
+    }
+
+    #[test]
+    fn known_ordering_should_return_none_for_other_graphs() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+
+        assert!(known_ordering(&graph).is_none());
+    }
+}
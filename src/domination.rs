@@ -0,0 +1,142 @@
+use crate::checkOrdering::positions;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+use std::collections::VecDeque;
+
+//Vertices at distance at most r from v, including v
+pub fn ball(graph: &EditGraph, v: Vertex, r: usize) -> VertexMap<usize> {
+    let mut dist: VertexMap<usize> = [(v, 0)].into_iter().collect();
+    let mut queue = VecDeque::from([v]);
+    while let Some(u) = queue.pop_front() {
+        let d = dist[&u];
+        if d == r {
+            continue;
+        }
+        for w in graph.neighbours(&u) {
+            if !dist.contains_key(w) {
+                dist.insert(*w, d + 1);
+                queue.push_back(*w);
+            }
+        }
+    }
+    dist
+}
+
+//Goes through the vertices in the order they are removed by compute_ordering and for each vertex v
+//that is not dominated yet adds the vertex at distance at most r from v that is removed last
+pub fn dominating_set(graph: &EditGraph, order: &[Vertex], r: usize) -> VertexSet {
+    let positions = positions(order);
+    let mut dominated = VertexSet::default();
+    let mut dominating = VertexSet::default();
+    for v in order {
+        if dominated.contains(v) {
+            continue;
+        }
+        let x = *ball(graph, *v, r).keys().max_by_key(|u| positions[u]).unwrap();
+        dominating.insert(x);
+        dominated.extend(ball(graph, x, r).into_keys());
+    }
+    dominating
+}
+
+//Proven approximation factor of dominating_set for an ordering with value at most p.
+//For r = 1, charge each added x to a vertex d of a minimum dominating set that dominates its witness
+//v, the undominated vertex x was added for. As x is removed last in N[v], it is d or removed after d.
+//Witnesses are distinct and each gives a different x, so the x charged to d are d, at most k <= p
+//neighbours of d in L, at most k more with a witness among those neighbours and the ends of 2-paths
+//d-v-x through R, which form a packing of size at most p - k. This gives at most 2p + 1 per vertex d.
+//For r = 2 the same argument needs paths of length 4, so the factor is only for r = 1
+pub fn approximation_factor(p: usize) -> usize {
+    2 * p + 1
+}
+
+//Vertices pairwise at distance more than 2r, which all need a different vertex to dominate them at
+//distance r so the size is a lower bound on the size of a minimum dominating set
+pub fn scattered_set(graph: &EditGraph, order: &[Vertex], r: usize) -> Vec<Vertex> {
+    let mut blocked = VertexSet::default();
+    let mut scattered = Vec::new();
+    for v in order {
+        if blocked.contains(v) {
+            continue;
+        }
+        scattered.push(*v);
+        blocked.extend(ball(graph, *v, 2 * r).into_keys());
+    }
+    scattered
+}
+
+pub fn is_dominating_set(graph: &EditGraph, dominating: &VertexSet, r: usize) -> bool {
+    let mut dominated = VertexSet::default();
+    for x in dominating {
+        dominated.extend(ball(graph, *x, r).into_keys());
+    }
+    graph.vertices().all(|v| dominated.contains(v))
+}
+
+#[cfg(test)]
+mod test_domination {
+    use crate::compute_ordering;
+    use crate::domination::{
+        approximation_factor, ball, dominating_set, is_dominating_set, scattered_set,
+    };
+    use crate::generators::graph_from_edges;
+    use graphbench::graph::VertexSet;
+
+    #[test]
+    fn ball_should_return_vertices_within_distance_r() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (1, 5)]);
+
+        let ball = ball(&graph, 1, 2);
+
+        assert_eq!(ball.len(), 4);
+        assert_eq!(ball[&3], 2);
+        assert!(!ball.contains_key(&4));
+    }
+
+    #[test]
+    fn dominating_set_should_dominate_within_approximation_factor() {
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (4, 5),
+            (5, 6),
+            (5, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 7),
+        ]);
+        let order = compute_ordering(2, &graph, true).unwrap();
+
+        for r in [1, 2] {
+            let dominating = dominating_set(&graph, &order, r);
+            let lower_bound = scattered_set(&graph, &order, r).len();
+
+            assert!(is_dominating_set(&graph, &dominating, r));
+            assert!(lower_bound <= dominating.len());
+        }
+        let dominating = dominating_set(&graph, &order, 1);
+        //A minimum dominating set has 3 vertices, for example 1, 5 and 9
+        assert!(dominating.len() <= 3 * approximation_factor(2));
+    }
+
+    #[test]
+    fn dominating_set_should_pick_centre_of_star() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+        let order = vec![2, 3, 4, 5, 1];
+
+        let dominating = dominating_set(&graph, &order, 1);
+
+        assert_eq!(dominating, [1].iter().cloned().collect::<VertexSet>());
+    }
+
+    #[test]
+    fn is_dominating_set_should_find_undominated_vertices() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4)]);
+        let dominating: VertexSet = [1].iter().cloned().collect();
+
+        assert!(!is_dominating_set(&graph, &dominating, 2));
+        assert!(is_dominating_set(&graph, &[2].iter().cloned().collect(), 2));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::admGraph::AdmOptions;
+use crate::checkOrdering::{positions, vertex_p};
+use crate::find_p_above;
+use crate::lowerBound::degeneracy;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, MutableGraph, Vertex, VertexMap, VertexSet};
+
+//Keeps an ordering of a graph that changes by edge insertions and deletions. Only the values of
+//vertices close to a changed edge are computed again. When an insertion gives vertices a value above
+//the current p they are first moved to the end of the ordering, and only if that breaks p the whole
+//ordering is computed again from scratch. After deletions and repairs p is the p of the kept
+//ordering, which can be above the smallest p of the graph
+pub struct DynamicOrdering {
+    graph: EditGraph,
+    order: Vec<Vertex>,
+    positions: VertexMap<usize>,
+    values: VertexMap<usize>, //value of each vertex when it is removed in order
+    pub p: usize,
+    pub reruns: usize,  //number of times the ordering was computed from scratch
+    pub repairs: usize, //number of insertions fixed by moving vertices to the end of the ordering
+}
+
+impl DynamicOrdering {
+    //order is in the order vertices are removed by compute_ordering and contains every vertex of graph
+    pub fn new(graph: EditGraph, order: Vec<Vertex>) -> Self {
+        assert_eq!(order.len(), graph.num_vertices());
+        let mut dynamic = DynamicOrdering::empty(graph);
+        dynamic.order = order;
+        dynamic.compute_values();
+        dynamic
+    }
+
+    //Starts from the ordering for the smallest p of graph
+    pub fn from_graph(graph: EditGraph) -> Self {
+        let mut dynamic = DynamicOrdering::empty(graph);
+        dynamic.rerun();
+        dynamic
+    }
+
+    fn empty(graph: EditGraph) -> Self {
+        DynamicOrdering {
+            graph,
+            order: Vec::new(),
+            positions: VertexMap::default(),
+            values: VertexMap::default(),
+            p: 0,
+            reruns: 0,
+            repairs: 0,
+        }
+    }
+
+    pub fn graph(&self) -> &EditGraph {
+        &self.graph
+    }
+
+    pub fn order(&self) -> &[Vertex] {
+        &self.order
+    }
+
+    //New vertices are added at the end of the ordering, returns p after the insertion
+    pub fn insert_edge(&mut self, u: Vertex, v: Vertex) -> usize {
+        if u == v || self.graph.adjacent(&u, &v) {
+            return self.p;
+        }
+        for w in [u, v] {
+            if !self.graph.contains(&w) {
+                self.graph.add_vertex(&w);
+                self.positions.insert(w, self.order.len());
+                self.order.push(w);
+                self.values.insert(w, 0);
+            }
+        }
+        self.graph.add_edge(&u, &v);
+
+        let over: Vec<Vertex> = self
+            .update_values(u, v)
+            .into_iter()
+            .filter(|(_, value)| *value > self.p)
+            .map(|(w, _)| w)
+            .collect();
+        if over.is_empty() {
+            return self.p;
+        }
+        if self.repair(&over) {
+            self.repairs += 1;
+        } else {
+            //The old p is not a lower bound as deletions can leave it above the smallest p of the graph
+            self.rerun();
+        }
+        self.p
+    }
+
+    //Returns p after the deletion, which is never bigger than before
+    pub fn delete_edge(&mut self, u: Vertex, v: Vertex) -> usize {
+        if !self.graph.adjacent(&u, &v) {
+            return self.p;
+        }
+        let affected = self.affected(u, v);
+        let had_p = affected.iter().any(|w| self.values[w] == self.p);
+        self.graph.remove_edge(&u, &v);
+
+        self.update_values(u, v);
+        if had_p {
+            self.p = self.values.values().copied().max().unwrap_or(0);
+        }
+        self.p
+    }
+
+    //The value of x only depends on the neighbours of x and the neighbours of those in R, so changing
+    //the edge uv only changes the values of u, v and their neighbours
+    fn affected(&self, u: Vertex, v: Vertex) -> VertexSet {
+        let mut affected: VertexSet = [u, v].into_iter().collect();
+        affected.extend(self.graph.neighbours(&u).copied());
+        affected.extend(self.graph.neighbours(&v).copied());
+        affected
+    }
+
+    //Moves the vertices above p to the end of the ordering, keeping the order among them. A vertex in
+    //L of x stays in L, so only vertices that had a moved vertex in R change value, which are the moved
+    //vertices and the vertices within distance 2 of them. Keeps the new ordering if none is above p
+    fn repair(&mut self, over: &[Vertex]) -> bool {
+        let moved: VertexSet = over.iter().copied().collect();
+        let mut order: Vec<Vertex> = self.order.iter().filter(|w| !moved.contains(w)).copied().collect();
+        order.extend(self.order.iter().filter(|w| moved.contains(w)));
+        let positions = positions(&order);
+
+        let mut affected = moved.clone();
+        for w in &moved {
+            for x in self.graph.neighbours(w) {
+                affected.insert(*x);
+                affected.extend(self.graph.neighbours(x).copied());
+            }
+        }
+        let mut values = Vec::new();
+        for w in affected {
+            let value = vertex_p(&self.graph, w, &positions);
+            if value > self.p {
+                return false;
+            }
+            values.push((w, value));
+        }
+
+        self.order = order;
+        self.positions = positions;
+        self.values.extend(values);
+        self.p = self.values.values().copied().max().unwrap_or(0);
+        true
+    }
+
+    fn update_values(&mut self, u: Vertex, v: Vertex) -> Vec<(Vertex, usize)> {
+        let mut updated = Vec::new();
+        for w in self.affected(u, v) {
+            let value = vertex_p(&self.graph, w, &self.positions);
+            self.values.insert(w, value);
+            updated.push((w, value));
+        }
+        updated
+    }
+
+    fn compute_values(&mut self) {
+        self.positions = positions(&self.order);
+        self.values = self
+            .order
+            .iter()
+            .map(|v| (*v, vertex_p(&self.graph, *v, &self.positions)))
+            .collect();
+        self.p = self.values.values().copied().max().unwrap_or(0);
+    }
+
+    //Finds the ordering for the smallest p, starting from the degeneracy as no smaller p can work
+    fn rerun(&mut self) {
+        self.reruns += 1;
+        let degeneracy = degeneracy(&self.graph) as i32;
+        let (_, order) = find_p_above(
+            degeneracy,
+            degeneracy - 1,
+            &self.graph,
+            true,
+            &AdmOptions::default(),
+            &mut Vec::new(),
+        );
+        self.order = order.expect("the search ends at a p with an ordering");
+        self.compute_values();
+    }
+}
+
+#[cfg(test)]
+mod test_dynamic_ordering {
+    use crate::checkOrdering::ordering_p;
+    use crate::compute_ordering;
+    use crate::dynamicOrdering::DynamicOrdering;
+    use crate::generators::graph_from_edges;
+
+    #[test]
+    fn insert_edge_should_keep_ordering_if_p_still_holds() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1), (1, 5), (5, 6)]);
+        let mut dynamic = DynamicOrdering::from_graph(graph);
+        assert_eq!(dynamic.p, 2);
+        let order = dynamic.order().to_vec();
+
+        dynamic.insert_edge(6, 7);
+
+        assert_eq!(dynamic.p, 2);
+        assert_eq!(dynamic.reruns, 1);
+        assert_eq!(&dynamic.order()[..order.len()], &order[..]);
+        assert_eq!(ordering_p(dynamic.graph(), dynamic.order()), 2);
+    }
+
+    #[test]
+    fn insert_edge_should_rerun_if_p_is_broken() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4)]);
+        let mut dynamic = DynamicOrdering::from_graph(graph);
+        assert_eq!(dynamic.p, 1);
+
+        dynamic.insert_edge(4, 1);
+
+        assert_eq!(dynamic.p, 2);
+        assert_eq!(dynamic.reruns, 2);
+        assert_eq!(ordering_p(dynamic.graph(), dynamic.order()), 2);
+    }
+
+    #[test]
+    fn insert_edge_should_repair_by_moving_vertices_to_end() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4)]);
+        let mut dynamic = DynamicOrdering::new(graph, vec![2, 3, 4, 1]);
+        assert_eq!(dynamic.p, 1);
+
+        dynamic.insert_edge(2, 5);
+
+        assert_eq!(dynamic.p, 1);
+        assert_eq!(dynamic.repairs, 1);
+        assert_eq!(dynamic.reruns, 0);
+        assert_eq!(dynamic.order(), &[3, 4, 1, 5, 2]);
+        assert_eq!(ordering_p(dynamic.graph(), dynamic.order()), 1);
+    }
+
+    #[test]
+    fn rerun_should_find_p_below_p_of_kept_ordering() {
+        let graph = graph_from_edges(&[(4, 2), (5, 4), (5, 3), (3, 1), (4, 1), (4, 3)]);
+        let mut dynamic = DynamicOrdering::new(graph, vec![3, 4, 5, 2, 1]);
+        assert_eq!(dynamic.p, 3);
+
+        dynamic.insert_edge(3, 2);
+
+        assert_eq!(dynamic.p, 2);
+        assert_eq!(dynamic.repairs, 0);
+        assert_eq!(dynamic.reruns, 1);
+        assert_eq!(ordering_p(dynamic.graph(), dynamic.order()), 2);
+    }
+
+    #[test]
+    fn delete_edge_should_lower_p_without_rerun() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 1)]);
+        let mut dynamic = DynamicOrdering::from_graph(graph);
+        assert_eq!(dynamic.p, 2);
+
+        dynamic.delete_edge(1, 2);
+
+        assert_eq!(dynamic.p, 1);
+        assert_eq!(dynamic.reruns, 1);
+        assert_eq!(ordering_p(dynamic.graph(), dynamic.order()), 1);
+    }
+
+    #[test]
+    fn updates_should_keep_p_of_ordering() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 1), (1, 3)]);
+        let mut dynamic = DynamicOrdering::from_graph(graph);
+        let updates = [(2, 4, true), (2, 5, true), (1, 3, false), (6, 1, true), (6, 3, true)];
+
+        for (u, v, insert) in updates {
+            let p = if insert {
+                dynamic.insert_edge(u, v)
+            } else {
+                dynamic.delete_edge(u, v)
+            };
+            assert_eq!(p, ordering_p(dynamic.graph(), dynamic.order()));
+            assert!(compute_ordering(p, dynamic.graph(), false).is_some());
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use clap::Subcommand;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, MutableGraph, Vertex};
+
+//Small splitmix64 generator so generated graphs only depend on the seed and not on a crate version
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    //Uniform in 0..n, n must be positive
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    //Uniform in [0, 1)
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn empty_graph(n: usize) -> EditGraph {
+    let mut graph = EditGraph::new();
+    for v in 0..n as Vertex {
+        graph.add_vertex(&v);
+    }
+    graph
+}
+
+pub fn grid(rows: usize, cols: usize) -> EditGraph {
+    let mut graph = empty_graph(rows * cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            let v = (i * cols + j) as Vertex;
+            if j + 1 < cols {
+                graph.add_edge(&v, &(v + 1));
+            }
+            if i + 1 < rows {
+                graph.add_edge(&v, &(v + cols as Vertex));
+            }
+        }
+    }
+    graph
+}
+
+//n points placed uniformly in the unit square, joined when they are at distance at most radius. Points
+//are bucketed in cells of side at least radius so only neighbouring cells are compared, with at most
+//about n cells so a tiny radius does not allocate more cells than points
+pub fn random_geometric(n: usize, radius: f64, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let points: Vec<(f64, f64)> = (0..n).map(|_| (rng.unit(), rng.unit())).collect();
+    let max_cells = (n as f64).sqrt().ceil() as usize;
+    let cells = ((1.0 / radius).floor() as usize).min(max_cells).max(1);
+    let cell = |x: f64| ((x * cells as f64) as usize).min(cells - 1);
+    let mut buckets = vec![Vec::new(); cells * cells];
+    for (v, (x, y)) in points.iter().enumerate() {
+        buckets[cell(*x) * cells + cell(*y)].push(v);
+    }
+
+    let mut graph = empty_graph(n);
+    for (v, (x, y)) in points.iter().enumerate() {
+        let (cx, cy) = (cell(*x), cell(*y));
+        for nx in cx.saturating_sub(1)..=(cx + 1).min(cells - 1) {
+            for ny in cy.saturating_sub(1)..=(cy + 1).min(cells - 1) {
+                for u in &buckets[nx * cells + ny] {
+                    let (ux, uy) = points[*u];
+                    if *u > v && (ux - x).powi(2) + (uy - y).powi(2) <= radius * radius {
+                        graph.add_edge(&(v as Vertex), &(*u as Vertex));
+                    }
+                }
+            }
+        }
+    }
+    graph
+}
+
+//Preferential attachment starting from a clique on m + 1 vertices, every new vertex is joined to m
+//distinct vertices picked with probability proportional to their degree
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = empty_graph(n);
+    let mut endpoints: Vec<Vertex> = Vec::new(); //every vertex once for each edge it is in
+    let start = (m + 1).min(n);
+    for u in 0..start as Vertex {
+        for v in 0..u {
+            graph.add_edge(&u, &v);
+            endpoints.extend([u, v]);
+        }
+    }
+    for v in start as Vertex..n as Vertex {
+        let mut targets = Vec::with_capacity(m);
+        while targets.len() < m {
+            let u = endpoints[rng.below(endpoints.len() as u64) as usize];
+            if !targets.contains(&u) {
+                targets.push(u);
+            }
+        }
+        for u in targets {
+            graph.add_edge(&v, &u);
+            endpoints.extend([u, v]);
+        }
+    }
+    graph
+}
+
+//G(n, q) where every pair of vertices is an edge with probability q
+pub fn erdos_renyi(n: usize, q: f64, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = empty_graph(n);
+    for u in 0..n as Vertex {
+        for v in 0..u {
+            if rng.unit() < q {
+                graph.add_edge(&u, &v);
+            }
+        }
+    }
+    graph
+}
+
+//Apollonian network: starting from a triangle, each new vertex goes into a face picked uniformly,
+//including the outer face, and is joined to its three corners. Every face stays a triangle so this is a
+//maximal planar graph
+pub fn planar_triangulation(n: usize, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = empty_graph(n);
+    let start = n.min(3) as Vertex;
+    for u in 0..start {
+        for v in 0..u {
+            graph.add_edge(&u, &v);
+        }
+    }
+    let mut faces: Vec<[Vertex; 3]> = if n >= 3 { vec![[0, 1, 2], [0, 1, 2]] } else { Vec::new() };
+    for v in start..n as Vertex {
+        let i = rng.below(faces.len() as u64) as usize;
+        let [a, b, c] = faces[i];
+        for u in [a, b, c] {
+            graph.add_edge(&v, &u);
+        }
+        faces[i] = [a, b, v];
+        faces.push([a, v, c]);
+        faces.push([v, b, c]);
+    }
+    graph
+}
+
+//Tries n * max_degree / 2 random pairs of vertices and keeps those where both still have degree below
+//max_degree
+pub fn bounded_degree(n: usize, max_degree: usize, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = empty_graph(n);
+    if n < 2 {
+        return graph;
+    }
+    for _ in 0..n * max_degree / 2 {
+        let u = rng.below(n as u64) as Vertex;
+        let v = rng.below(n as u64) as Vertex;
+        if u != v
+            && (graph.degree(&u) as usize) < max_degree
+            && (graph.degree(&v) as usize) < max_degree
+        {
+            graph.add_edge(&u, &v);
+        }
+    }
+    graph
+}
+
+//A centre, vertex 0, joined to every vertex of count disjoint cliques with size vertices each
+pub fn star_of_cliques(count: usize, size: usize) -> EditGraph {
+    let mut graph = empty_graph(1 + count * size);
+    for i in 0..count {
+        let clique: Vec<Vertex> = (0..size).map(|j| (1 + i * size + j) as Vertex).collect();
+        for (k, u) in clique.iter().enumerate() {
+            graph.add_edge(&0, u);
+            for v in &clique[..k] {
+                graph.add_edge(u, v);
+            }
+        }
+    }
+    graph
+}
+
+//Sides 0..a and a..a + b, with 2-admissibility min(a, b)
+pub fn complete_bipartite(a: usize, b: usize) -> EditGraph {
+    let mut graph = empty_graph(a + b);
+    for u in 0..a as Vertex {
+        for v in a as Vertex..(a + b) as Vertex {
+            graph.add_edge(&u, &v);
+        }
+    }
+    graph
+}
+
+pub fn path(n: usize) -> EditGraph {
+    let mut graph = empty_graph(n);
+    for v in 1..n as Vertex {
+        graph.add_edge(&(v - 1), &v);
+    }
+    graph
+}
+
+pub fn cycle(n: usize) -> EditGraph {
+    let mut graph = path(n);
+    if n >= 3 {
+        graph.add_edge(&0, &(n as Vertex - 1));
+    }
+    graph
+}
+
+pub fn complete(n: usize) -> EditGraph {
+    let mut graph = empty_graph(n);
+    for u in 0..n as Vertex {
+        for v in 0..u {
+            graph.add_edge(&u, &v);
+        }
+    }
+    graph
+}
+
+//Random recursive tree, each new vertex is joined to a vertex picked uniformly among the earlier ones
+pub fn random_tree(n: usize, seed: u64) -> EditGraph {
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = empty_graph(n);
+    for v in 1..n as Vertex {
+        let u = rng.below(v as u64) as Vertex;
+        graph.add_edge(&u, &v);
+    }
+    graph
+}
+
+fn parse_radius(s: &str) -> Result<f64, String> {
+    let radius: f64 = s.parse().map_err(|_| format!("{s} is not a number"))?;
+    if radius.is_finite() && radius > 0.0 {
+        Ok(radius)
+    } else {
+        Err(format!("radius must be finite and above 0 but is {s}"))
+    }
+}
+
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let q: f64 = s.parse().map_err(|_| format!("{s} is not a number"))?;
+    if (0.0..=1.0).contains(&q) {
+        Ok(q)
+    } else {
+        Err(format!("probability must be between 0 and 1 but is {s}"))
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Family {
+    /// Grid with rows x cols vertices
+    Grid { rows: usize, cols: usize },
+    /// Random planar triangulation built by inserting vertices into faces
+    Triangulation {
+        n: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Random graph where no vertex has more than max_degree neighbours
+    BoundedDegree {
+        n: usize,
+        max_degree: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Barabási–Albert preferential attachment where each new vertex gets m edges
+    PreferentialAttachment {
+        n: usize,
+        m: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Random geometric graph on the unit square
+    Geometric {
+        n: usize,
+        #[arg(value_parser = parse_radius)]
+        radius: f64,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Erdős–Rényi graph where each pair of vertices is an edge with probability q
+    ErdosRenyi {
+        n: usize,
+        #[arg(value_parser = parse_probability)]
+        q: f64,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// A centre joined to every vertex of count disjoint cliques on size vertices
+    StarOfCliques { count: usize, size: usize },
+    /// Complete bipartite graph K_{a,b}
+    CompleteBipartite { a: usize, b: usize },
+    /// Path on n vertices
+    Path { n: usize },
+    /// Cycle on n vertices
+    Cycle { n: usize },
+    /// Complete graph K_n
+    Complete { n: usize },
+    /// Random recursive tree on n vertices
+    Tree {
+        n: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+}
+
+impl Family {
+    pub fn generate(&self) -> EditGraph {
+        match *self {
+            Family::Grid { rows, cols } => grid(rows, cols),
+            Family::Triangulation { n, seed } => planar_triangulation(n, seed),
+            Family::BoundedDegree { n, max_degree, seed } => bounded_degree(n, max_degree, seed),
+            Family::PreferentialAttachment { n, m, seed } => barabasi_albert(n, m, seed),
+            Family::Geometric { n, radius, seed } => random_geometric(n, radius, seed),
+            Family::ErdosRenyi { n, q, seed } => erdos_renyi(n, q, seed),
+            Family::StarOfCliques { count, size } => star_of_cliques(count, size),
+            Family::CompleteBipartite { a, b } => complete_bipartite(a, b),
+            Family::Path { n } => path(n),
+            Family::Cycle { n } => cycle(n),
+            Family::Complete { n } => complete(n),
+            Family::Tree { n, seed } => random_tree(n, seed),
+        }
+    }
+}
+
+//Builds the graph with the given edges for tests, an edge listed twice is added once
+#[cfg(test)]
+pub fn graph_from_edges(edges: &[(u32, u32)]) -> EditGraph {
+    let mut graph = EditGraph::new();
+    let edges: graphbench::graph::EdgeSet = edges.iter().cloned().collect();
+    for (u, v) in edges.iter() {
+        graph.add_edge(u, v);
+    }
+    graph
+}
+
+#[cfg(test)]
+mod test_generators {
+    use crate::generators::{
+        barabasi_albert, bounded_degree, complete_bipartite, erdos_renyi, grid, parse_probability,
+        parse_radius, planar_triangulation, random_geometric, star_of_cliques,
+    };
+    use graphbench::graph::Graph;
+
+    #[test]
+    fn grid_should_have_edges_between_adjacent_cells() {
+        let graph = grid(3, 4);
+
+        assert_eq!(graph.num_vertices(), 12);
+        assert_eq!(graph.num_edges(), 3 * 3 + 2 * 4);
+    }
+
+    #[test]
+    fn barabasi_albert_should_add_m_edges_per_vertex() {
+        let graph = barabasi_albert(100, 3, 1);
+
+        assert_eq!(graph.num_vertices(), 100);
+        assert_eq!(graph.num_edges(), 6 + 96 * 3);
+    }
+
+    #[test]
+    fn random_graphs_should_only_depend_on_seed() {
+        assert_eq!(erdos_renyi(50, 0.1, 7).num_edges(), erdos_renyi(50, 0.1, 7).num_edges());
+        assert_eq!(
+            random_geometric(200, 0.1, 7).num_edges(),
+            random_geometric(200, 0.1, 7).num_edges()
+        );
+        assert_eq!(erdos_renyi(30, 1.0, 7).num_edges(), 30 * 29 / 2);
+    }
+
+    #[test]
+    fn random_geometric_should_handle_tiny_and_large_radius() {
+        assert_eq!(random_geometric(100, 1e-300, 7).num_edges(), 0);
+        assert_eq!(random_geometric(100, 1e-300, 7).num_vertices(), 100);
+        assert_eq!(random_geometric(30, 2.0, 7).num_edges(), 30 * 29 / 2);
+    }
+
+    #[test]
+    fn parse_should_reject_radius_and_probability_out_of_range() {
+        assert_eq!(parse_radius("0.5"), Ok(0.5));
+        for radius in ["0", "-1", "NaN", "inf", "x"] {
+            assert!(parse_radius(radius).is_err(), "{radius}");
+        }
+        assert_eq!(parse_probability("0"), Ok(0.0));
+        assert_eq!(parse_probability("1"), Ok(1.0));
+        for q in ["-0.1", "1.5", "NaN", "x"] {
+            assert!(parse_probability(q).is_err(), "{q}");
+        }
+    }
+
+    #[test]
+    fn planar_triangulation_should_have_3n_minus_6_edges() {
+        let graph = planar_triangulation(50, 3);
+
+        assert_eq!(graph.num_vertices(), 50);
+        assert_eq!(graph.num_edges(), 3 * 50 - 6);
+    }
+
+    #[test]
+    fn bounded_degree_should_not_exceed_max_degree() {
+        let graph = bounded_degree(200, 4, 5);
+
+        assert!(graph.vertices().all(|v| graph.degree(v) <= 4));
+        assert!(graph.num_edges() > 0);
+    }
+
+    #[test]
+    fn star_of_cliques_and_complete_bipartite_should_have_expected_edges() {
+        assert_eq!(star_of_cliques(3, 4).num_edges(), 3 * (4 + 6));
+        assert_eq!(complete_bipartite(3, 5).num_edges(), 15);
+        assert_eq!(complete_bipartite(3, 5).num_vertices(), 8);
+    }
+}
@@ -0,0 +1,719 @@
+//The module files keep their camelCase names
+#![allow(non_snake_case)]
+pub mod admGraph;
+pub mod augmentingPath;
+
+pub mod admData;
+pub mod bench;
+pub mod checkOrdering;
+pub mod colouring;
+pub mod components;
+pub mod domination;
+pub mod dynamicOrdering;
+pub mod generators;
+pub mod lowerBound;
+pub mod neighbourhoodCover;
+pub mod reduction;
+pub mod regression;
+pub mod runResult;
+pub mod subgraphCount;
+pub mod subgraphOrdering;
+pub mod vertexLabels;
+pub mod witnessExport;
+
+use crate::admGraph::{AdmGraph, AdmOptions, AdmStats, Discrepancy, Witnesses};
+use crate::components::{connected_components, induced_subgraph, known_ordering, ComponentCounts, MaxComponent};
+use crate::lowerBound::{degeneracy, stalled_set_bound};
+use crate::runResult::{LowerBoundSummary, Probe};
+use crate::vertexLabels::{load_labelled_graph, VertexLabels};
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::*;
+use std::cmp::{max, Reverse};
+use std::time::Instant;
+
+pub fn load_graph(network_path: String, network: &String) -> (EditGraph, VertexLabels) {
+    let file_dir = format!("{}/{}.txt.gz", network_path, network);
+    load_labelled_graph(&file_dir)
+        .unwrap_or_else(|_| panic!("Error occurred loading graph {}", network))
+}
+
+pub fn next_p_value(p: i32, is_p: bool, lowest_p: i32, highest_not_p: i32) -> i32 {
+    //Stop where the lowest p is p or the highest p + 1 is p
+    if (p - highest_not_p <= 1 && is_p) || (lowest_p != -1 && (p - lowest_p).abs() == 1) {
+        return -1;
+    }
+    //Continue to double the p value we check if we haven't found a value where G is p,2 admissible,
+    //starting from 1 as doubling 0 would check 0 again
+    if lowest_p == -1 && !is_p {
+        return max(p, 1) * 2;
+    }
+    //Once we found a p value keep halving the search between the lowest p and the highest not p
+    let x = max(p, lowest_p);
+    (x + highest_not_p) / 2
+}
+
+pub fn compute_ordering(p: usize, graph: &EditGraph, save_order: bool) -> Option<Vec<Vertex>> {
+    compute_ordering_with_options(p, graph, save_order, &AdmOptions::default())
+}
+
+pub fn compute_ordering_with_options(
+    p: usize,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+) -> Option<Vec<Vertex>> {
+    compute_ordering_with_stats(p, graph, save_order, options).0
+}
+
+//Also returns the counters of the elimination so slow probes can be explained, and the vertices where
+//the incremental matching disagreed with a maximum packing when checking them
+pub fn compute_ordering_with_stats(
+    p: usize,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+) -> (Option<Vec<Vertex>>, AdmStats, Vec<Discrepancy>) {
+    let mut adm_graph = AdmGraph::with_options(graph, *options);
+    let order = run_elimination(&mut adm_graph, p, graph, save_order, options);
+    (order, adm_graph.stats, adm_graph.discrepancies)
+}
+
+//Runs the elimination for p to the end, returning the order vertices were removed in if all of them could be
+pub fn run_elimination(
+    adm_graph: &mut AdmGraph,
+    p: usize,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+) -> Option<Vec<Vertex>> {
+    let mut order = Vec::default();
+
+    if options.prune_low_degree {
+        let pruned = adm_graph.prune_low_degree(p);
+        if save_order {
+            order.extend(pruned);
+        }
+    } else {
+        adm_graph.initialise_candidates(p);
+    }
+
+    let mut next_vertex = adm_graph.remove_v_from_candidates(p);
+    while next_vertex.is_some() && !adm_graph.is_all_vertices_in_r_or_candidates() {
+        let v = next_vertex.unwrap();
+        if save_order {
+            order.push(v);
+        }
+        next_vertex = adm_graph.remove_v_from_candidates(p);
+    }
+    if save_order {
+        order.extend(next_vertex.iter()); // Adds vertex if not None
+    }
+
+    let found_order = adm_graph.is_all_vertices_in_r_or_candidates();
+
+    if found_order {
+        if save_order {
+            order.extend(adm_graph.candidates.iter());
+            assert_eq!(order.len(), graph.num_vertices());
+        }
+        Some(order)
+    } else {
+        None
+    }
+}
+
+//Also returns the 2-paths in M of each vertex when it moved to R, for exporting. The ordering is always saved
+pub fn compute_ordering_with_witnesses(
+    p: usize,
+    graph: &EditGraph,
+    options: &AdmOptions,
+) -> Option<(Vec<Vertex>, Witnesses)> {
+    let options = AdmOptions {
+        record_witnesses: true,
+        ..*options
+    };
+    let mut adm_graph = AdmGraph::with_options(graph, options);
+    let order = run_elimination(&mut adm_graph, p, graph, true, &options)?;
+    adm_graph.record_candidate_witnesses();
+    Some((order, adm_graph.witnesses))
+}
+
+//Vertices left in L when the elimination for p stalls, None if it finds an ordering
+pub fn stalled_set(p: usize, graph: &EditGraph, options: &AdmOptions) -> Option<VertexSet> {
+    let mut adm_graph = AdmGraph::with_options(graph, *options);
+    match run_elimination(&mut adm_graph, p, graph, false, options) {
+        Some(_) => None,
+        None => Some(adm_graph.vertices_in_l().clone()),
+    }
+}
+
+//Lower bounds on p that do not rely on the incremental matching of the elimination
+pub fn compute_lower_bound(p: i32, graph: &EditGraph, options: &AdmOptions) -> LowerBoundSummary {
+    let degeneracy = degeneracy(graph);
+    let stalled_set = if p > 0 {
+        stalled_set(p as usize - 1, graph, options).map(|stalled| stalled_set_bound(graph, &stalled))
+    } else {
+        None
+    };
+    LowerBoundSummary {
+        degeneracy,
+        stalled_set,
+        best: max(degeneracy, stalled_set.unwrap_or(0)),
+    }
+}
+
+//Searches for the smallest p such that the graph is p,2 admissible starting the search at p
+pub fn find_p(
+    p: i32,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+    history: &mut Vec<Probe>,
+) -> (i32, Option<Vec<Vertex>>) {
+    find_p_above(p, -1, graph, save_order, options, history)
+}
+
+//Same as find_p when the graph is already known not to be p,2 admissible for highest_not_p, so the
+//search does not check any p up to it again
+pub fn find_p_above(
+    mut p: i32,
+    mut highest_not_p: i32,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+    history: &mut Vec<Probe>,
+) -> (i32, Option<Vec<Vertex>>) {
+    let mut lowest_p: i32 = -1;
+    let mut best_order = None;
+    p = max(p, highest_not_p + 1);
+
+    if graph.num_vertices() == 0 {
+        return (0, Some(Vec::new()));
+    }
+
+    loop {
+        let probe_start = Instant::now();
+        let (result, stats, discrepancies) = compute_ordering_with_stats(p as usize, graph, save_order, options);
+        let mut found_better = false;
+        if let Some(order) = result {
+            assert!(lowest_p == -1 || p < lowest_p);
+            lowest_p = p;
+            best_order = Some(order);
+            found_better = true;
+        } else {
+            assert!(p > highest_not_p);
+            highest_not_p = p;
+        }
+        history.push(Probe {
+            p: p as usize,
+            is_p: found_better,
+            time_ms: probe_start.elapsed().as_secs_f64() * 1000.0,
+            stats,
+            discrepancies,
+            component: None,
+        });
+
+        let next_p = next_p_value(p, found_better, lowest_p, highest_not_p);
+        if next_p == -1 {
+            if !found_better {
+                p = lowest_p;
+            }
+            break;
+        }
+        p = next_p;
+    }
+
+    (p, best_order)
+}
+
+//The 2-admissibility of a graph is the max over its connected components so each component is solved
+//separately, returns p, the combined ordering and the number of components, the number that needed
+//the p search and the component attaining the max
+pub fn find_p_by_component(
+    start_p: i32,
+    graph: &EditGraph,
+    save_order: bool,
+    options: &AdmOptions,
+    history: &mut Vec<Probe>,
+) -> (i32, Option<Vec<Vertex>>, ComponentCounts) {
+    let mut components = connected_components(graph);
+    //Solving the largest components first gives a p that most of the smaller components can be checked against
+    components.sort_by_key(|c| Reverse(c.len()));
+
+    let mut p = 0;
+    let mut order = Vec::new();
+    let mut solved = 0;
+    let mut max_component = None;
+
+    for (index, component) in components.iter().enumerate() {
+        let subgraph = induced_subgraph(graph, component);
+        let probes_before = history.len();
+        let (component_p, component_order) = match known_ordering(&subgraph) {
+            Some((known_p, known_order)) => (known_p, Some(known_order)),
+            None => {
+                solved += 1;
+                //Any other component has p at least 2 so only search if it is not p for the current max
+                let probe_start = Instant::now();
+                let result = if p >= 2 {
+                    let (result, stats, discrepancies) =
+                        compute_ordering_with_stats(p as usize, &subgraph, save_order, options);
+                    history.push(Probe {
+                        p: p as usize,
+                        is_p: result.is_some(),
+                        time_ms: probe_start.elapsed().as_secs_f64() * 1000.0,
+                        stats,
+                        discrepancies,
+                        component: None,
+                    });
+                    result
+                } else {
+                    None
+                };
+                //Either p was just checked or p is below 2, which the component contains a cycle for
+                let highest_not_p = max(p, 1);
+                match result {
+                    Some(component_order) => (p, Some(component_order)),
+                    None => find_p_above(start_p, highest_not_p, &subgraph, save_order, options, history),
+                }
+            }
+        };
+        for probe in &mut history[probes_before..] {
+            probe.component = Some(index);
+        }
+
+        if max_component.is_none() || component_p > p {
+            p = component_p;
+            max_component = Some(MaxComponent {
+                vertex: *component.iter().min().unwrap(),
+                num_vertices: subgraph.num_vertices(),
+                num_edges: subgraph.num_edges(),
+            });
+        }
+        if save_order {
+            order.extend(component_order.unwrap());
+        }
+    }
+
+    let order = if save_order { Some(order) } else { None };
+    (p, order, (components.len(), solved, max_component))
+}
+
+#[cfg(test)]
+mod test_main {
+
+    use crate::admGraph::AdmOptions;
+    use crate::augmentingPath::SearchStrategy;
+    use crate::checkOrdering::ordering_p;
+    use crate::generators::grid;
+    use crate::{compute_ordering, compute_ordering_with_stats, find_p, find_p_by_component};
+    use graphbench::editgraph::EditGraph;
+    use graphbench::graph::{EdgeSet, MutableGraph};
+
+    #[test]
+    pub fn compute_ordering_returns_true_if_all_v_in_g_has_neighbours_on_or_below_p() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (1, 5),
+            (2, 6),
+            (3, 6),
+            (4, 6),
+            (5, 6),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+
+        assert!(compute_ordering(4, &graph, false).is_some());
+    }
+
+    #[test]
+    pub fn compute_ordering_returns_true_for_correct_p_value() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+
+        assert!(compute_ordering(4, &graph, false).is_some());
+    }
+
+    #[test]
+    pub fn compute_ordering_returns_false_for_incorrect_p_value() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
+            .iter()
+            .cloned()
+            .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+
+        assert!(compute_ordering(2, &graph, false).is_none());
+    }
+
+    #[test]
+    pub fn test_admissibility_returns_correct_p_value() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [
+            (1, 2),
+            (1, 9),
+            (2, 3),
+            (2, 9),
+            (3, 4),
+            (3, 7),
+            (3, 9),
+            (4, 5),
+            (4, 6),
+            (5, 6),
+            (5, 8),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+
+        let mut p = 1;
+        loop {
+            let is_p = compute_ordering(p, &graph, false);
+            if is_p.is_some() {
+                break;
+            }
+            p += 1;
+        }
+
+        assert_eq!(p, 3);
+    }
+
+    #[test]
+    pub fn find_p_starting_at_zero_finds_p() {
+        let graph = grid(3, 4);
+
+        let (p, order) = find_p(0, &graph, true, &AdmOptions::default(), &mut Vec::new());
+
+        assert_eq!(p, 3);
+        assert_eq!(ordering_p(&graph, &order.unwrap()), 3);
+    }
+
+    #[test]
+    pub fn find_p_by_component_returns_max_p_over_components() {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [
+            //K4
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            //Tree
+            (5, 6),
+            (5, 7),
+            (7, 8),
+            //Cycle
+            (9, 10),
+            (10, 11),
+            (11, 9),
+            //K5
+            (12, 13),
+            (12, 14),
+            (12, 15),
+            (12, 16),
+            (13, 14),
+            (13, 15),
+            (13, 16),
+            (14, 15),
+            (14, 16),
+            (15, 16),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        graph.add_vertex(&17);
+
+        let (p, order, (count, solved, max_component)) =
+            find_p_by_component(2, &graph, true, &AdmOptions::default(), &mut Vec::new());
+        let (whole_p, _) = find_p(2, &graph, false, &AdmOptions::default(), &mut Vec::new());
+
+        assert_eq!(p, whole_p);
+        assert_eq!(count, 5);
+        assert_eq!(solved, 2);
+        assert_eq!(order.unwrap().len(), 17);
+        let max_component = max_component.unwrap();
+        assert_eq!(max_component.vertex, 12);
+        assert_eq!(max_component.num_vertices, 5);
+        assert_eq!(max_component.num_edges, 10);
+    }
+
+    #[test]
+    pub fn find_p_by_component_does_not_check_p_known_to_fail_again() {
+        //A 3x4 grid with p = 3 is solved first, the K5 with p = 4 fails for 3 and needs one more probe
+        let mut graph = grid(3, 4);
+        for u in 12..17 {
+            for v in 12..u {
+                graph.add_edge(&u, &v);
+            }
+        }
+        let mut history = Vec::new();
+
+        let (p, _, _) = find_p_by_component(1, &graph, false, &AdmOptions::default(), &mut history);
+
+        assert_eq!(p, 4);
+        let k5_probes: Vec<(usize, bool)> =
+            history.iter().filter(|probe| probe.component == Some(1)).map(|probe| (probe.p, probe.is_p)).collect();
+        assert_eq!(k5_probes, [(3, false), (4, true)]);
+        assert!(history.iter().all(|probe| probe.component.is_some()));
+    }
+
+    //Graph with many 2-paths between its vertices and a few low degree vertices for pruning
+    fn options_fixture() -> EditGraph {
+        let mut graph = EditGraph::new();
+        let edges: EdgeSet = [
+            (1, 2),
+            (1, 5),
+            (1, 9),
+            (2, 3),
+            (2, 6),
+            (2, 9),
+            (3, 4),
+            (3, 7),
+            (3, 9),
+            (4, 5),
+            (4, 6),
+            (4, 8),
+            (4, 12),
+            (5, 6),
+            (5, 8),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 11),
+            (11, 1),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        for (u, v) in edges.iter() {
+            graph.add_edge(u, v);
+        }
+        graph
+    }
+
+    #[test]
+    pub fn compute_ordering_with_options_returns_same_result_as_default() {
+        let graph = options_fixture();
+        let variants = [
+            AdmOptions { prune_low_degree: true, ..AdmOptions::default() },
+            AdmOptions { search: SearchStrategy::Phase, ..AdmOptions::default() },
+            AdmOptions { skip_n1_repair: true, ..AdmOptions::default() },
+            AdmOptions { skip_l2_repair: true, ..AdmOptions::default() },
+            AdmOptions { skip_n1_repair: true, skip_l2_repair: true, ..AdmOptions::default() },
+            AdmOptions { exact_check: true, ..AdmOptions::default() },
+        ];
+
+        for options in &variants {
+            for p in 1..7 {
+                let (result, _, discrepancies) = compute_ordering_with_stats(p, &graph, true, options);
+                assert!(discrepancies.is_empty(), "{options:?} for p = {p}");
+                assert_eq!(result.is_some(), compute_ordering(p, &graph, false).is_some(), "{options:?} for p = {p}");
+                if let Some(order) = result {
+                    assert!(ordering_p(&graph, &order) <= p, "{options:?} for p = {p}");
+                }
+            }
+        }
+    }
+}
+
+//Graph families where the 2-admissibility is known, checked with the whole search for p from a start
+//below and a start above the value
+#[cfg(test)]
+mod test_known_values {
+    use crate::admGraph::AdmOptions;
+    use crate::checkOrdering::ordering_p;
+    use crate::generators::{
+        complete, complete_bipartite, cycle, grid, path, random_tree, star_of_cliques,
+    };
+    use crate::{find_p, find_p_by_component};
+    use graphbench::editgraph::EditGraph;
+
+    fn assert_p(graph: &EditGraph, expected: usize, family: &str) {
+        for start in [1, 2 * expected as i32 + 3] {
+            let (p, order) = find_p(start, graph, true, &AdmOptions::default(), &mut Vec::new());
+            assert_eq!(p, expected as i32, "{family} starting at {start}");
+            assert_eq!(ordering_p(graph, &order.unwrap()), expected, "{family} starting at {start}");
+        }
+        let (p, _, _) = find_p_by_component(1, graph, false, &AdmOptions::default(), &mut Vec::new());
+        assert_eq!(p, expected as i32, "{family} by component");
+    }
+
+    #[test]
+    fn paths_have_p_1() {
+        assert_p(&path(1), 0, "P1");
+        for n in 2..40 {
+            assert_p(&path(n), 1, &format!("P{n}"));
+        }
+    }
+
+    #[test]
+    fn cycles_have_p_2() {
+        for n in 3..40 {
+            assert_p(&cycle(n), 2, &format!("C{n}"));
+        }
+    }
+
+    #[test]
+    fn stars_have_p_1() {
+        for k in 1..30 {
+            assert_p(&complete_bipartite(1, k), 1, &format!("K1,{k}"));
+        }
+    }
+
+    #[test]
+    fn complete_graphs_have_p_n_minus_1() {
+        for n in 1..12 {
+            assert_p(&complete(n), n - 1, &format!("K{n}"));
+        }
+    }
+
+    #[test]
+    fn complete_bipartite_graphs_have_p_of_smaller_side() {
+        for a in 1..8 {
+            for b in 1..8 {
+                assert_p(&complete_bipartite(a, b), a.min(b), &format!("K{a},{b}"));
+            }
+        }
+    }
+
+    //Checked against all orderings for grids up to 4x4. Larger grids contain a 3x3 grid
+    #[test]
+    fn grids_have_p_3_from_3x3() {
+        for rows in 1..8 {
+            for cols in rows..8 {
+                let expected = match rows {
+                    1 if cols == 1 => 0,
+                    1 => 1,
+                    2 => 2,
+                    _ => 3,
+                };
+                assert_p(&grid(rows, cols), expected, &format!("{rows}x{cols} grid"));
+            }
+        }
+    }
+
+    #[test]
+    fn trees_have_p_1() {
+        for n in 2..60 {
+            for seed in 0..3 {
+                assert_p(&random_tree(n, seed), 1, &format!("tree on {n} vertices with seed {seed}"));
+            }
+        }
+    }
+
+    //The centre and a clique form a clique on size + 1 vertices, which needs p = size
+    #[test]
+    fn stars_of_cliques_have_p_of_clique_size() {
+        for count in 1..5 {
+            for size in 1..6 {
+                assert_p(&star_of_cliques(count, size), size, &format!("{count} cliques of size {size}"));
+            }
+        }
+    }
+}
+
+//Edge lists of random labels, comments, extra columns and bytes that are not UTF-8, gzipped as in the
+//corpus and read as load_graph does. The number of inputs tried can be raised with FUZZ_ITERATIONS
+#[cfg(test)]
+mod test_fuzz {
+    use crate::admGraph::AdmOptions;
+    use crate::augmentingPath::SearchStrategy;
+    use crate::checkOrdering::ordering_p;
+    use crate::vertexLabels::read_labelled_graph;
+    use crate::{compute_ordering, compute_ordering_with_options, find_p};
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use graphbench::graph::{Graph, MutableGraph, VertexSet};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::io::Write;
+
+    //Tokens after the first 8 are not valid UTF-8 or not ASCII
+    const TOKENS: [&[u8]; 12] = [
+        b" ", b"\t", b"\n", b"\r\n", b"#", b"%", b"1.5", b"-", b"\xff", b"\xc3", b"e\xcc\x81", b"0",
+    ];
+
+    fn random_edge_list(rng: &mut StdRng) -> Vec<u8> {
+        let labels = rng.gen_range(1..25);
+        let tokens = if rng.gen_bool(0.2) { TOKENS.len() } else { 8 };
+        let mut bytes = Vec::new();
+        for _ in 0..rng.gen_range(0..60) {
+            match rng.gen_range(0..10) {
+                0 => bytes.extend_from_slice(TOKENS[rng.gen_range(0..tokens)]),
+                1 if tokens > 8 => bytes.push(rng.gen()),
+                _ => bytes.extend_from_slice(
+                    format!("{} {}\n", rng.gen_range(0..labels), rng.gen_range(0..labels)).as_bytes(),
+                ),
+            }
+        }
+        bytes
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn fuzz_loaded_graphs_should_get_ordering_of_smallest_p() {
+        let iterations = std::env::var("FUZZ_ITERATIONS").map_or(500, |n| n.parse().unwrap());
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..iterations {
+            let bytes = random_edge_list(&mut rng);
+            let mut gzipped = gzip(&bytes);
+            //Files cut short or not gzipped should give an error
+            if rng.gen_bool(0.1) {
+                gzipped.truncate(rng.gen_range(0..gzipped.len()));
+            } else if rng.gen_bool(0.05) {
+                gzipped = bytes.clone();
+            }
+            let Ok((mut graph, labels)) = read_labelled_graph(GzDecoder::new(&gzipped[..])) else {
+                continue;
+            };
+            graph.remove_loops();
+            assert_eq!(graph.num_vertices(), labels.len());
+
+            let start_p = rng.gen_range(0..4);
+            let (p, order) = find_p(start_p, &graph, true, &AdmOptions::default(), &mut Vec::new());
+            let order = order.unwrap();
+            let input = String::from_utf8_lossy(&bytes);
+            assert_eq!(order.len(), graph.num_vertices(), "{input}");
+            assert_eq!(order.iter().copied().collect::<VertexSet>().len(), order.len(), "{input}");
+            assert_eq!(ordering_p(&graph, &order) as i32, p, "{input}");
+            assert!(p == 0 || compute_ordering(p as usize - 1, &graph, false).is_none(), "{input}");
+            let options = AdmOptions {
+                search: SearchStrategy::Phase,
+                ..AdmOptions::default()
+            };
+            assert!(compute_ordering_with_options(p as usize, &graph, false, &options).is_some(), "{input}");
+        }
+    }
+}
+
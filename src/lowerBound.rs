@@ -0,0 +1,69 @@
+use crate::checkOrdering::max_packing;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+use std::collections::BTreeSet;
+
+//Removes a vertex of minimum degree until the graph is empty, returning each vertex with its degree
+//when it was removed
+fn peel(graph: &EditGraph) -> Vec<(Vertex, usize)> {
+    let mut degrees: VertexMap<usize> =
+        graph.vertices().map(|v| (*v, graph.degree(v) as usize)).collect();
+    let mut queue: BTreeSet<(usize, Vertex)> = degrees.iter().map(|(v, d)| (*d, *v)).collect();
+    let mut peeled = Vec::with_capacity(degrees.len());
+    while let Some((d, v)) = queue.pop_first() {
+        degrees.remove(&v);
+        peeled.push((v, d));
+        for u in graph.neighbours(&v) {
+            if let Some(du) = degrees.get_mut(u) {
+                queue.remove(&(*du, *u));
+                *du -= 1;
+                queue.insert((*du, *u));
+            }
+        }
+    }
+    peeled
+}
+
+//The value of a vertex is at least its number of neighbours in L, so p is at least the smallest
+//possible largest number of neighbours in L, which is the degeneracy
+pub fn degeneracy(graph: &EditGraph) -> usize {
+    peel(graph).iter().map(|(_, d)| *d).max().unwrap_or(0)
+}
+
+//When the elimination for some p stalls, the first vertex of the stalled set L in any ordering has
+//all other vertices of L after it. Its value can only be larger than with all vertices outside L in R,
+//so p is at least the smallest value in L with R the vertices outside L. The values are computed with a
+//maximum packing from scratch so this does not rely on the incremental matching
+pub fn stalled_set_bound(graph: &EditGraph, stalled: &VertexSet) -> usize {
+    let in_l = |u: &Vertex| stalled.contains(u);
+    stalled
+        .iter()
+        .map(|v| graph.neighbours(v).filter(|u| in_l(u)).count() + max_packing(graph, *v, &in_l))
+        .min()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test_lower_bound {
+    use crate::generators::graph_from_edges;
+    use crate::lowerBound::{degeneracy, stalled_set_bound};
+    use graphbench::graph::VertexSet;
+
+    #[test]
+    fn degeneracy_should_be_size_of_clique_minus_one() {
+        //K4 with a pendant path attached
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4), (4, 5), (5, 6)]);
+
+        assert_eq!(degeneracy(&graph), 3);
+    }
+
+    #[test]
+    fn stalled_set_bound_should_count_paths_through_vertices_outside_set() {
+        //A 4-cycle 1-2-3-4 where every pair of opposite vertices also has a common neighbour outside
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 1), (1, 5), (5, 3), (2, 6), (6, 4)]);
+        let stalled: VertexSet = [1, 2, 3, 4].iter().cloned().collect();
+
+        assert_eq!(stalled_set_bound(&graph, &stalled), 3);
+        assert_eq!(degeneracy(&graph), 2);
+    }
+}
@@ -1,32 +1,48 @@
 #![allow(warnings)]
-mod admGraph;
-mod augmentingPath;
-
-mod admData;
-
-use crate::admGraph::AdmGraph;
+use admissibility_rust::admGraph::AdmOptions;
+use admissibility_rust::augmentingPath::SearchStrategy;
+use admissibility_rust::bench::{self, bench_smallest_p, generated_graphs};
+use admissibility_rust::colouring::{colour_bound, distance_2_colouring, num_colours};
+use admissibility_rust::domination::{approximation_factor, dominating_set, is_dominating_set, scattered_set};
+use admissibility_rust::dynamicOrdering::DynamicOrdering;
+use admissibility_rust::generators::Family;
+use admissibility_rust::neighbourhoodCover::{is_neighbourhood_cover, neighbourhood_cover, overlap, overlap_bound};
+use admissibility_rust::reduction::reduce;
+use admissibility_rust::regression::{self, available_networks, load_expected, rerun, Comparison, PeakMemory};
+use admissibility_rust::runResult::{
+    ordering_to_csv, ComponentSummary, Memory, OutputFormat, ReductionSummary, RunResult,
+};
+use admissibility_rust::subgraphCount::{count_subgraphs, degree_ordering};
+use admissibility_rust::subgraphOrdering::subgraph_ordering;
+use admissibility_rust::vertexLabels::{load_edge_changes, load_labels, load_ordering, write_graph, VertexLabels};
+use admissibility_rust::witnessExport::{to_dot, to_graphml, ExportFormat};
+use admissibility_rust::{
+    compute_lower_bound, compute_ordering_with_witnesses, find_p, find_p_by_component, load_graph,
+};
 use clap::{Parser, Subcommand};
 use graphbench::editgraph::EditGraph;
 use graphbench::graph::*;
-use peak_alloc::PeakAlloc;
 use std::cmp::max;
 
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use peak_alloc::PeakAlloc;
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
+use std::time::Instant;
 
 #[global_allocator]
 static PEAK_ALLOC: PeakAlloc = PeakAlloc;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
-#[command(propagate_version = true)]
+#[command(propagate_version = true, allow_missing_positional = true)]
 struct Args {
     /// network file name
     network: String,
 
-    /// start p value
+    /// start p value, not used by generate
+    #[arg(default_value_t = 1)]
     p: i32,
 
     /// Path to network
@@ -36,6 +52,46 @@ struct Args {
     /// Whether to track memory consumption
     track_memory: bool,
 
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    /// Format to report the run in and save the ordering as
+    format: OutputFormat,
+
+    #[clap(short, long, default_value_t = false)]
+    /// Whether to solve each connected component separately
+    components: bool,
+
+    #[clap(short, long, default_value_t = false)]
+    /// Whether to remove pendant vertices, degree 2 chains and degree 2 twins before the p search
+    reduce: bool,
+
+    #[clap(short, long, default_value_t = false)]
+    /// Whether to move vertices of degree at most p to R before the elimination for each p checked
+    prune: bool,
+
+    #[clap(short, long, value_enum, default_value_t = SearchStrategy::Dfs)]
+    /// How to search for augmenting paths, dfs finds one path per search and phase finds several shortest paths
+    search: SearchStrategy,
+
+    #[clap(long, default_value_t = false)]
+    /// Whether to skip adding a vertex moving to R to the matching of its neighbours, leaving it to the augmenting path search
+    no_n1_repair: bool,
+
+    #[clap(long, default_value_t = false)]
+    /// Whether to skip replacing matching edges removed when their vertex in L moves to R, leaving it to the augmenting path search
+    no_l2_repair: bool,
+
+    #[clap(long, default_value_t = false)]
+    /// Whether to check the invariants of the elimination after each vertex moves to R, this is slow
+    validate: bool,
+
+    #[clap(long, default_value_t = false)]
+    /// Whether to recompute the maximum packing from scratch at each check and report vertices where it disagrees, this is slow
+    exact_check: bool,
+
+    #[clap(long, default_value_t = false)]
+    /// Whether to compute lower bounds on p from the degeneracy and the vertices left when the elimination for p - 1 stalls
+    lower_bounds: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -48,15 +104,70 @@ enum Commands {
         #[arg(default_value= "results")]
         path: String,
     },
+    /// Restrict a saved ordering to the subgraph induced by a list of vertices and report its p
+    Subgraph {
+        /// Saved ordering: one vertex per line (gzipped if it ends in .gz), or a .csv or .json file of the save command
+        ordering: String,
+        /// Vertices of the subgraph with one vertex per line, gzipped if it ends in .gz
+        vertices: String,
+        #[arg(long, default_value_t = 0)]
+        /// Number of smaller p values to try with compute_ordering on the subgraph
+        refine: usize,
+    },
+    /// Apply edge insertions and deletions to the ordering of the smallest p and report how p changes
+    Update {
+        /// Changes with one edge per line as + u v or - u v, gzipped if it ends in .gz
+        changes: String,
+    },
+    /// Colour the square of the graph greedily in reverse order of the ordering and save the colouring
+    Colour {
+        /// The path to save the colouring to
+        #[arg(default_value = "results")]
+        path: String,
+    },
+    /// Find a dominating set greedily from the ordering and check it dominates
+    Dominate,
+    /// Build clusters such that every vertex has its closed neighbourhood in one cluster and save them
+    Cover {
+        /// The path to save the clusters to
+        #[arg(default_value = "results")]
+        path: String,
+    },
+    /// Count triangles, 4-cycles and 2-paths with edges oriented by the ordering and by increasing degree
+    Count,
+    /// Save the graph with the 2-paths in the matching of each vertex when it moved to R, labelled by ordering position
+    Export {
+        /// The path to save the graph to
+        #[arg(default_value = "results")]
+        path: String,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Dot)]
+        /// File format of the graph
+        format: ExportFormat,
+    },
+    /// Time each phase of the elimination with each search strategy for the smallest p and the p below it, on the network and generated graphs
+    Bench {
+        #[arg(long, default_value_t = 5)]
+        /// Number of timed runs of each elimination, after one warm up run
+        repeat: usize,
+    },
+    /// Generate a graph and save it as <NETWORK_PATH>/<NETWORK>.txt.gz instead of loading it, p is not used and can be left out
+    Generate {
+        #[command(subcommand)]
+        family: Family,
+    },
+    /// Solve the networks again and compare p, vertex and edge counts with the saved results, NETWORK can list several separated by commas
+    Regress {
+        /// The results to compare with
+        #[arg(long, default_value = "results/results.csv")]
+        results: String,
+        #[arg(long, default_value_t = false)]
+        /// Whether to check every network of the results that is in NETWORK_PATH instead of NETWORK
+        all: bool,
+    },
 }
 
-fn load_graph(network_path: String, network: &String) -> EditGraph {
-    let file_dir = format!("{}/{}.txt.gz", network_path, network);
-    EditGraph::from_gzipped(&file_dir)
-        .unwrap_or_else(|_| panic!("Error occurred loading graph {}", network))
-}
 
-fn save_ordering_to_file(path: String, network: String, order: Vec<Vertex>) {
+fn save_ordering_to_file(path: String, network: String, order: Vec<String>) {
     let folder = PathBuf::from(path);;
     std::fs::create_dir_all(&folder).unwrap();
     let file_path = folder.join(network.as_str().to_owned() + ".txt.gz");
@@ -71,49 +182,166 @@ fn save_ordering_to_file(path: String, network: String, order: Vec<Vertex>) {
     gz.finish().unwrap();
 }
 
-fn next_p_value(p: i32, is_p: bool, lowest_p: i32, highest_not_p: i32) -> i32 {
-    //Stop where the lowest p is p or the highest p + 1 is p
-    if (p - highest_not_p <= 1 && is_p) || (p - lowest_p).abs() == 1 {
-        return -1;
+//Summaries of the commands go to stderr in the json and csv formats so stdout only holds the run
+fn print_summary(format: OutputFormat, summary: String) {
+    match format {
+        OutputFormat::Text => println!("{}", summary),
+        _ => eprintln!("{}", summary),
     }
-    //Continue to double the p value we check if we haven't found a value where G is p,2 admissible
-    if lowest_p == -1 && !is_p {
-        return (p * 2) as i32;
+}
+
+fn save_colouring_to_file(path: &str, network: &str, labels: &VertexLabels, colouring: &VertexMap<usize>) {
+    let folder = PathBuf::from(path);
+    std::fs::create_dir_all(&folder).unwrap();
+    let file_path = folder.join(network.to_owned() + ".colouring.txt.gz");
+
+    let file = std::fs::File::create(file_path).unwrap();
+    let mut gz = GzEncoder::new(file, Compression::default());
+
+    let mut vertices: Vec<&Vertex> = colouring.keys().collect();
+    vertices.sort();
+    for v in vertices {
+        writeln!(gz, "{} {}", labels.label(*v), colouring[v]).unwrap();
     }
-    //Once we found a p value keep halving the search between the lowest p and the highest not p
-    let x = max(p, lowest_p);
-    return (x + highest_not_p) / 2;
+
+    gz.finish().unwrap();
 }
 
-fn compute_ordering(p: usize, graph: &EditGraph, save_order: bool) -> Option<Vec<Vertex>> {
-    let mut adm_graph = AdmGraph::new(graph);
+//One cluster per line, its centre followed by a colon and the vertices of the cluster
+fn save_cover_to_file(path: &str, network: &str, labels: &VertexLabels, clusters: &[(Vertex, VertexSet)]) {
+    let folder = PathBuf::from(path);
+    std::fs::create_dir_all(&folder).unwrap();
+    let file_path = folder.join(format!("{}.cover.txt.gz", network));
 
-    adm_graph.initialise_candidates(p);
+    let file = std::fs::File::create(file_path).unwrap();
+    let mut gz = GzEncoder::new(file, Compression::default());
 
-    let mut next_vertex = adm_graph.remove_v_from_candidates(p);
-    let mut order = Vec::default();
-    while next_vertex.is_some() && !adm_graph.is_all_vertices_in_r_or_candidates() {
-        let v = next_vertex.unwrap();
-        if save_order {
-            order.push(v);
-        }
-        next_vertex = adm_graph.remove_v_from_candidates(p);
+    for (centre, cluster) in clusters {
+        let mut vertices: Vec<&Vertex> = cluster.iter().collect();
+        vertices.sort();
+        let members: Vec<&str> = vertices.iter().map(|v| labels.label(**v)).collect();
+        writeln!(gz, "{}: {}", labels.label(*centre), members.join(" ")).unwrap();
     }
-    if save_order {
-        order.extend(next_vertex.iter()); // Adds vertex if not None
+
+    gz.finish().unwrap();
+}
+
+fn save_witnesses_to_file(path: &str, network: &str, format: ExportFormat, contents: &str) {
+    let folder = PathBuf::from(path);
+    std::fs::create_dir_all(&folder).unwrap();
+    let file_path = folder.join(format!("{}.witnesses.{}", network, format.extension()));
+    std::fs::write(file_path, contents).unwrap();
+}
+
+fn save_graph_to_file(network_path: &str, network: &str, graph: &EditGraph) {
+    let folder = PathBuf::from(network_path);
+    std::fs::create_dir_all(&folder).unwrap();
+    let file = std::fs::File::create(folder.join(network.to_owned() + ".txt.gz")).unwrap();
+    let mut gz = GzEncoder::new(file, Compression::default());
+    write_graph(graph, &mut gz).unwrap();
+    gz.finish().unwrap();
+}
+
+fn save_result_to_file(path: String, result: &RunResult, format: OutputFormat) {
+    let folder = PathBuf::from(path);
+    std::fs::create_dir_all(&folder).unwrap();
+    match format {
+        OutputFormat::Json => {
+            std::fs::write(folder.join(format!("{}.json", result.network)), result.to_json()).unwrap();
+        }
+        //A csv row cannot hold the ordering as a table so it is saved next to the run
+        OutputFormat::Csv => {
+            std::fs::write(folder.join(format!("{}.csv", result.network)), result.to_csv()).unwrap();
+            let ordering = ordering_to_csv(result.ordering.as_ref().unwrap());
+            std::fs::write(folder.join(format!("{}.ordering.csv", result.network)), ordering).unwrap();
+        }
+        OutputFormat::Text => unreachable!("Text orderings are saved with save_ordering_to_file"),
     }
+}
 
-    let found_order = adm_graph.is_all_vertices_in_r_or_candidates();
 
-    if found_order {
-        if save_order {
-            order.extend(adm_graph.candidates.iter());
-            assert_eq!(order.len(), graph.num_vertices());
+//Reports the p of a saved ordering restricted to the subgraph induced by a list of vertices
+//Returns an error instead of panicking on a file that can not be read or a vertex that is not in the graph
+fn query_subgraph(
+    graph: &EditGraph,
+    labels: &VertexLabels,
+    ordering_path: &str,
+    vertices_path: &str,
+    refine: usize,
+) -> Result<(), String> {
+    let order = load_ordering(ordering_path)
+        .map_err(|e| format!("Error occurred loading ordering {}: {}", ordering_path, e))?
+        .iter()
+        .map(|label| {
+            labels
+                .get_id(label)
+                .ok_or_else(|| format!("Vertex {} of the ordering {} is not in the graph", label, ordering_path))
+        })
+        .collect::<Result<Vec<Vertex>, String>>()?;
+    let vertex_labels = load_labels(vertices_path)
+        .map_err(|e| format!("Error occurred loading vertices {}: {}", vertices_path, e))?;
+    let mut unknown: Vec<&str> = vertex_labels
+        .iter()
+        .filter(|label| labels.get_id(label).is_none())
+        .map(|label| label.as_str())
+        .collect();
+    unknown.sort();
+    unknown.dedup();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "{} vertices of {} are not in the graph: {}",
+            unknown.len(),
+            vertices_path,
+            unknown.join(" ")
+        ));
+    }
+    let vertices: VertexSet = vertex_labels.iter().filter_map(|label| labels.get_id(label)).collect();
+
+    let subgraph = subgraph_ordering(graph, &order, &vertices, refine);
+    println!(
+        "Subgraph has {} vertices and {} edges",
+        subgraph.graph.num_vertices(),
+        subgraph.graph.num_edges()
+    );
+    println!("p of the restricted ordering is {}", subgraph.restricted_p);
+    if refine > 0 {
+        println!("p after refining is {}", subgraph.p);
+    }
+    Ok(())
+}
+
+fn update_ordering(graph: &EditGraph, labels: &VertexLabels, changes_path: &str) -> Result<(), String> {
+    let changes = load_edge_changes(changes_path)
+        .map_err(|e| format!("Error occurred loading changes {}: {}", changes_path, e))?;
+    let mut labels = labels.clone();
+    let mut dynamic = DynamicOrdering::from_graph(graph.clone());
+    println!("p is {}", dynamic.p);
+
+    for (i, (insert, u, v)) in changes.iter().enumerate() {
+        let previous_p = dynamic.p;
+        let p = if *insert {
+            let (u, v) = (labels.id(u), labels.id(v));
+            dynamic.insert_edge(u, v)
+        } else {
+            let id = |label: &String| {
+                labels
+                    .get_id(label)
+                    .ok_or_else(|| format!("Vertex {} of change {} is not in the graph", label, i + 1))
+            };
+            dynamic.delete_edge(id(u)?, id(v)?)
+        };
+        if p != previous_p {
+            println!("p is {} after change {}: {} {} {}", p, i + 1, if *insert { "+" } else { "-" }, u, v);
         }
-        Some(order)
-    } else {
-        None
     }
+    println!(
+        "p is {} after {} changes with {} local repairs and {} full reruns",
+        dynamic.p,
+        changes.len(),
+        dynamic.repairs,
+        dynamic.reruns - 1
+    );
+    Ok(())
 }
 
 fn main() {
@@ -121,163 +349,297 @@ fn main() {
 
     let network_path = args.network_path;
     let network = args.network;
-    let mut p = args.p;
+    let p = args.p;
 
     let track_memory = args.track_memory;
+    let format = args.format;
 
-    let save_path = match args.command {
-        None => None,
-        Some(Commands::Save { path }) => Some(path)
+    let save_path = match &args.command {
+        Some(Commands::Save { path }) => Some(path.clone()),
+        _ => None,
+    };
+    let colour_path = match &args.command {
+        Some(Commands::Colour { path }) => Some(path.clone()),
+        _ => None,
     };
+    let dominate = matches!(args.command, Some(Commands::Dominate));
+    let cover = match &args.command {
+        Some(Commands::Cover { path }) => Some(path.clone()),
+        _ => None,
+    };
+    let count = matches!(args.command, Some(Commands::Count));
+    let keep_order =
+        save_path.is_some() || colour_path.is_some() || dominate || cover.is_some() || count;
+
+    let mut history = Vec::new();
+
+    let mut memory_load = 0.0;
+
+    if let Some(Commands::Generate { family }) = &args.command {
+        let graph = family.generate();
+        save_graph_to_file(&network_path, &network, &graph);
+        println!(
+            "Saved {} with {} vertices and {} edges to {}/{}.txt.gz",
+            network,
+            graph.num_vertices(),
+            graph.num_edges(),
+            network_path,
+            network
+        );
+        return;
+    }
 
-    let mut lowest_p: i32 = -1;
-    let mut highest_not_p: i32 = -1;
-    let mut best_order = None;
+    let options = AdmOptions {
+        prune_low_degree: args.prune,
+        search: args.search,
+        skip_n1_repair: args.no_n1_repair,
+        skip_l2_repair: args.no_l2_repair,
+        validate: args.validate,
+        exact_check: args.exact_check,
+        record_witnesses: false,
+        time_searches: false,
+    };
 
-    let mut peak_mem : f32;
+    if let Some(Commands::Regress { results, all }) = &args.command {
+        let expected = load_expected(results).unwrap_or_else(|e| panic!("Error occurred loading results {}", e));
+        let networks = if *all {
+            available_networks(&expected, &network_path)
+        } else {
+            network.split(',').map(|n| n.to_string()).collect()
+        };
+        let memory = PeakMemory {
+            reset: || PEAK_ALLOC.reset_peak_usage(),
+            peak_kb: || PEAK_ALLOC.peak_usage_as_kb() as f64,
+        };
+        let comparisons: Vec<Comparison> = networks
+            .into_iter()
+            .map(|network| {
+                let actual = rerun(&network_path, &network, p, args.components, track_memory.then_some(&memory), &options);
+                let expected = expected.iter().find(|e| e.network == network).cloned();
+                Comparison { network, expected, actual }
+            })
+            .collect();
+        print!("{}", regression::to_text(&comparisons));
+        if comparisons.iter().any(|c| !c.mismatches().is_empty()) {
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    let mut graph = load_graph(network_path, &network);
+    let start = Instant::now();
+    let (mut graph, labels) = load_graph(network_path, &network);
 
     graph.remove_loops();
+    let load_time = start.elapsed();
 
     if track_memory{
-        peak_mem = PEAK_ALLOC.peak_usage_as_kb();
-        println!("Max memory used after graph loading in kb is {}", peak_mem);
+        memory_load = PEAK_ALLOC.peak_usage_as_kb();
     }
 
-    loop {
-        let result = compute_ordering(p as usize, &graph, save_path.is_some());
-        let mut found_better = false;
-        if let Some(order) = result {
-            assert!(lowest_p == -1 || p < lowest_p);
-            lowest_p = p;
-            best_order = Some(order);
-            found_better = true;
-        } else {
-            assert!(p > highest_not_p);
-            highest_not_p = p;
+    if let Some(Commands::Subgraph { ordering, vertices, refine }) = &args.command {
+        if let Err(e) = query_subgraph(&graph, &labels, ordering, vertices, *refine) {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
+        return;
+    }
 
-        let next_p = next_p_value(p, found_better, lowest_p, highest_not_p);
-        if next_p == -1 {
-            if !found_better {
-                p = lowest_p;
-            }
-            break;
+    if let Some(Commands::Update { changes }) = &args.command {
+        if let Err(e) = update_ordering(&graph, &labels, changes) {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
-        p = next_p;
+        return;
     }
 
-    println!("p is {}", p);
-
-    if track_memory {
-        peak_mem = PEAK_ALLOC.peak_usage_as_kb();
-        println!("Max memory used in total kb is {}", peak_mem);
+    if let Some(Commands::Bench { repeat }) = &args.command {
+        let mut graphs = vec![(network.clone(), graph)];
+        graphs.extend(generated_graphs());
+        for (name, graph) in &graphs {
+            print!("{}", bench::to_text(&bench_smallest_p(name, graph, *repeat, &options)));
+        }
+        return;
     }
 
-    match save_path {
-        None => {}
-        Some(path) => {
-            if let Some(order) = best_order {
-                save_ordering_to_file(path, network, order);
-            }
+    let reduction = if args.reduce { Some(reduce(&graph)) } else { None };
+    let search_graph = reduction.as_ref().map_or(&graph, |(reduced, _)| reduced);
+
+    let (mut p, mut best_order, components) = if args.components {
+        let (p, order, summary) =
+            find_p_by_component(p, search_graph, keep_order, &options, &mut history);
+        (p, order, Some(summary))
+    } else {
+        let (p, order) = find_p(p, search_graph, keep_order, &options, &mut history);
+        (p, order, None)
+    };
+
+    for probe in &history {
+        for discrepancy in &probe.discrepancies {
+            eprintln!(
+                "p = {}: vertex {} has value {} from the incremental matching but {} from a maximum packing",
+                probe.p,
+                labels.label(discrepancy.vertex),
+                discrepancy.incremental,
+                discrepancy.exact
+            );
         }
     }
-}
 
-#[cfg(test)]
-mod test_main {
-
-    use crate::compute_ordering;
-    use graphbench::editgraph::EditGraph;
-    use graphbench::graph::{EdgeSet, MutableGraph};
-
-    #[test]
-    pub fn compute_ordering_returns_true_if_all_v_in_g_has_neighbours_on_or_below_p() {
-        let mut graph = EditGraph::new();
-        let edges: EdgeSet = [
-            (1, 2),
-            (1, 3),
-            (1, 4),
-            (1, 5),
-            (2, 6),
-            (3, 6),
-            (4, 6),
-            (5, 6),
-        ]
-        .iter()
-        .cloned()
-        .collect();
-        for (u, v) in edges.iter() {
-            graph.add_edge(u, v);
+    if let Some((_, reduction)) = &reduction {
+        p = max(p, reduction.prefix_p);
+        best_order = best_order.map(|order| reduction.lift(order));
+    }
+    let colouring = match (&colour_path, &best_order) {
+        (Some(_), Some(order)) => Some(distance_2_colouring(&graph, order)),
+        _ => None,
+    };
+    let dominating = match (dominate, &best_order) {
+        (true, Some(order)) => Some((dominating_set(&graph, order, 1), scattered_set(&graph, order, 1).len())),
+        _ => None,
+    };
+    let clusters = match (&cover, &best_order) {
+        (Some(_), Some(order)) => Some(neighbourhood_cover(&graph, order, 1)),
+        _ => None,
+    };
+    let counts = match (count, &best_order) {
+        (true, Some(order)) => {
+            let ordered_start = Instant::now();
+            let ordered = count_subgraphs(&graph, order);
+            let ordered_time = ordered_start.elapsed();
+            let degree_start = Instant::now();
+            let degree = count_subgraphs(&graph, &degree_ordering(&graph));
+            Some((ordered, ordered_time, degree, degree_start.elapsed()))
         }
-
-        assert!(compute_ordering(4, &graph).is_some());
+        _ => None,
+    };
+    if save_path.is_none() {
+        best_order = None;
     }
 
-    #[test]
-    pub fn compute_ordering_returns_true_for_correct_p_value() {
-        let mut graph = EditGraph::new();
-        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
-            .iter()
-            .cloned()
-            .collect();
-        for (u, v) in edges.iter() {
-            graph.add_edge(u, v);
-        }
+    let lower_bound = if args.lower_bounds {
+        Some(compute_lower_bound(p, &graph, &options))
+    } else {
+        None
+    };
+
+    let memory = if track_memory {
+        Some(Memory {
+            load_kb: memory_load,
+            total_kb: PEAK_ALLOC.peak_usage_as_kb(),
+        })
+    } else {
+        None
+    };
 
-        assert!(compute_ordering(4, &graph).is_some());
+    let result = RunResult {
+        network,
+        p,
+        num_vertices: graph.num_vertices(),
+        num_edges: graph.num_edges(),
+        history,
+        load_time_ms: load_time.as_secs_f64() * 1000.0,
+        total_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+        memory,
+        components: components.map(|(count, solved, max_component)| ComponentSummary {
+            count,
+            solved,
+            max_vertex: max_component.as_ref().map(|c| labels.label(c.vertex).to_string()),
+            max_vertices: max_component.as_ref().map_or(0, |c| c.num_vertices),
+            max_edges: max_component.as_ref().map_or(0, |c| c.num_edges),
+        }),
+        reduction: reduction.as_ref().map(|(reduced, reduction)| ReductionSummary {
+            removed: graph.num_vertices() - reduced.num_vertices(),
+            prefix_p: reduction.prefix_p,
+            reduced_vertices: reduced.num_vertices(),
+            reduced_edges: reduced.num_edges(),
+        }),
+        lower_bound,
+        ordering: best_order.map(|order: Vec<Vertex>| labels.labels_of(&order)),
+    };
+
+    print!("{}", result.format(format));
+
+    if let (Some(path), Some(colouring)) = (colour_path, colouring) {
+        let max_degree = graph.vertices().map(|v| graph.degree(v) as usize).max().unwrap_or(0);
+        print_summary(
+            format,
+            format!(
+                "Distance 2 colouring uses {} colours, the bound from p and max degree {} is {}",
+                num_colours(&colouring),
+                max_degree,
+                colour_bound(p as usize, max_degree)
+            ),
+        );
+        save_colouring_to_file(&path, &result.network, &labels, &colouring);
     }
 
-    #[test]
-    pub fn compute_ordering_returns_false_for_incorrect_p_value() {
-        let mut graph = EditGraph::new();
-        let edges: EdgeSet = [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]
-            .iter()
-            .cloned()
-            .collect();
-        for (u, v) in edges.iter() {
-            graph.add_edge(u, v);
-        }
+    if let Some((dominating, lower_bound)) = dominating {
+        print_summary(
+            format,
+            format!(
+                "Dominating set has {} vertices (approximation factor {}), at least {} are needed, dominates: {}",
+                dominating.len(),
+                approximation_factor(p as usize),
+                lower_bound,
+                is_dominating_set(&graph, &dominating, 1)
+            ),
+        );
+    }
 
-        assert!(!compute_ordering(2, &graph).is_some());
+    if let (Some(path), Some(clusters)) = (cover, clusters) {
+        print_summary(
+            format,
+            format!(
+                "Neighbourhood cover has {} clusters with overlap {} (bound {}), covers: {}",
+                clusters.len(),
+                overlap(&clusters),
+                overlap_bound(p as usize),
+                is_neighbourhood_cover(&graph, &clusters, 1)
+            ),
+        );
+        save_cover_to_file(&path, &result.network, &labels, &clusters);
     }
 
-    #[test]
-    pub fn test_admissibility_returns_correct_p_value() {
-        let mut graph = EditGraph::new();
-        let edges: EdgeSet = [
-            (1, 2),
-            (1, 9),
-            (2, 3),
-            (2, 9),
-            (3, 4),
-            (3, 7),
-            (3, 9),
-            (4, 5),
-            (4, 6),
-            (5, 6),
-            (5, 8),
-            (6, 7),
-            (7, 8),
-            (8, 9),
-        ]
-        .iter()
-        .cloned()
-        .collect();
-        for (u, v) in edges.iter() {
-            graph.add_edge(u, v);
-        }
+    if let Some((ordered, ordered_time, degree, degree_time)) = counts {
+        print_summary(
+            format,
+            format!(
+                "{} triangles, {} 4-cycles and {} 2-paths counted in {:.3} ms with the ordering and {:.3} ms by degree, counts agree: {}",
+                ordered.triangles,
+                ordered.four_cycles,
+                ordered.two_paths,
+                ordered_time.as_secs_f64() * 1000.0,
+                degree_time.as_secs_f64() * 1000.0,
+                ordered == degree
+            ),
+        );
+    }
+
+    if let Some(Commands::Export { path, format }) = &args.command {
+        //The elimination is run again for the p found on the whole graph, as the witnesses are not kept
+        //during the search and the components or reduction may have produced the ordering
+        let (order, witnesses) = compute_ordering_with_witnesses(p as usize, &graph, &options)
+            .expect("The elimination should succeed for the p found");
+        let contents = match format {
+            ExportFormat::Dot => to_dot(&graph, &labels, &order, &witnesses),
+            ExportFormat::Graphml => to_graphml(&graph, &labels, &order, &witnesses),
+        };
+        save_witnesses_to_file(path, &result.network, *format, &contents);
+    }
 
-        let mut p = 1;
-        loop {
-            let is_p = compute_ordering(p, &graph);
-            if is_p.is_some() {
-                break;
+    match save_path {
+        None => {}
+        Some(path) => {
+            if result.ordering.is_some() {
+                match format {
+                    OutputFormat::Text => save_ordering_to_file(
+                        path,
+                        result.network.clone(),
+                        result.ordering.clone().unwrap(),
+                    ),
+                    _ => save_result_to_file(path, &result, format),
+                }
             }
-            p += 1;
         }
-
-        assert_eq!(p, 3);
     }
 }
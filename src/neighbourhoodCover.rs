@@ -0,0 +1,149 @@
+use crate::checkOrdering::positions;
+use crate::domination::ball;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+use std::collections::VecDeque;
+
+//The vertex removed last among the vertices u reachable from v by a path of length at most r on which
+//u is removed last, so every other vertex of the path is removed before u
+fn centre(graph: &EditGraph, positions: &VertexMap<usize>, v: Vertex, r: usize) -> Vertex {
+    let mut centre = v;
+    let mut stack = vec![(v, positions[&v], 0)]; //end of the walk, last position on it and its length
+    while let Some((u, last, length)) = stack.pop() {
+        if positions[&u] == last && positions[&u] > positions[&centre] {
+            centre = u;
+        }
+        if length == r {
+            continue;
+        }
+        for w in graph.neighbours(&u) {
+            stack.push((*w, last.max(positions[w]), length + 1));
+        }
+    }
+    centre
+}
+
+//Vertices reachable from c by a path of length at most radius that only uses vertices removed no later
+//than c
+fn cluster(graph: &EditGraph, positions: &VertexMap<usize>, c: Vertex, radius: usize) -> VertexSet {
+    let mut dist: VertexMap<usize> = [(c, 0)].into_iter().collect();
+    let mut queue = VecDeque::from([c]);
+    while let Some(u) = queue.pop_front() {
+        let d = dist[&u];
+        if d == radius {
+            continue;
+        }
+        for w in graph.neighbours(&u) {
+            if positions[w] <= positions[&c] && !dist.contains_key(w) {
+                dist.insert(*w, d + 1);
+                queue.push_back(*w);
+            }
+        }
+    }
+    dist.into_keys().collect()
+}
+
+//Clusters such that the vertices at distance at most r from any vertex are all in one cluster. Every
+//vertex v picks its centre c and the cluster of c holds the vertices reached from c by paths of length
+//at most 2r through vertices removed no later than c. A path of length at most r from v to some x has no
+//vertex removed after c, otherwise the one removed last would be picked as centre, so joining it with
+//the path from v to c puts x in the cluster. Clusters are returned with their centre, in the order the
+//centres are removed
+pub fn neighbourhood_cover(graph: &EditGraph, order: &[Vertex], r: usize) -> Vec<(Vertex, VertexSet)> {
+    let positions = positions(order);
+    let centres: VertexSet = order.iter().map(|v| centre(graph, &positions, *v, r)).collect();
+    order
+        .iter()
+        .filter(|c| centres.contains(c))
+        .map(|c| (*c, cluster(graph, &positions, *c, 2 * r)))
+        .collect()
+}
+
+//Largest number of clusters that contain the same vertex
+pub fn overlap(clusters: &[(Vertex, VertexSet)]) -> usize {
+    let mut count: VertexMap<usize> = VertexMap::default();
+    for (_, cluster) in clusters {
+        for v in cluster {
+            *count.entry(*v).or_insert(0) += 1;
+        }
+    }
+    count.values().copied().max().unwrap_or(0)
+}
+
+//Bound on overlap for an ordering with value at most p. The centres of the clusters containing x are
+//reached from x by a path of length at most 2r on which the centre is removed last. For r = 1 these are
+//x, its k <= p neighbours in L, at most p neighbours in L of each of those, and the ends of 2-paths
+//x-w-c through R. A maximum packing of those has at most p - k paths so by Konig's theorem at most
+//p - k vertices cover them, each covering at most p ends. This gives at most p^2 + p + 1 clusters.
+//For r = 2 the centres are reached by paths of length 4, so the bound is only for r = 1
+pub fn overlap_bound(p: usize) -> usize {
+    p * p + p + 1
+}
+
+//Checks that for every vertex the vertices at distance at most r are all in the same cluster
+pub fn is_neighbourhood_cover(graph: &EditGraph, clusters: &[(Vertex, VertexSet)], r: usize) -> bool {
+    graph.vertices().all(|v| {
+        let neighbourhood = ball(graph, *v, r);
+        clusters
+            .iter()
+            .any(|(_, cluster)| neighbourhood.keys().all(|u| cluster.contains(u)))
+    })
+}
+
+#[cfg(test)]
+mod test_neighbourhood_cover {
+    use crate::checkOrdering::ordering_p;
+    use crate::compute_ordering;
+    use crate::generators::graph_from_edges;
+    use crate::neighbourhoodCover::{
+        is_neighbourhood_cover, neighbourhood_cover, overlap, overlap_bound,
+    };
+    use graphbench::graph::VertexSet;
+
+    #[test]
+    fn neighbourhood_cover_should_contain_neighbourhoods_with_bounded_overlap() {
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 1),
+            (1, 5),
+            (5, 6),
+            (6, 7),
+            (7, 8),
+            (8, 5),
+            (8, 9),
+        ]);
+        let order = compute_ordering(2, &graph, true).unwrap();
+        let p = ordering_p(&graph, &order);
+
+        for r in [1, 2] {
+            let clusters = neighbourhood_cover(&graph, &order, r);
+
+            assert!(is_neighbourhood_cover(&graph, &clusters, r));
+        }
+        let clusters = neighbourhood_cover(&graph, &order, 1);
+        assert!(overlap(&clusters) <= overlap_bound(p));
+    }
+
+    #[test]
+    fn neighbourhood_cover_of_star_should_be_one_cluster() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4)]);
+
+        let clusters = neighbourhood_cover(&graph, &[2, 3, 4, 1], 1);
+
+        assert_eq!(clusters, vec![(1, [1, 2, 3, 4].iter().cloned().collect::<VertexSet>())]);
+    }
+
+    #[test]
+    fn is_neighbourhood_cover_should_find_split_neighbourhoods() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3)]);
+        let clusters = vec![
+            (1, [1, 2].iter().cloned().collect()),
+            (3, [2, 3].iter().cloned().collect()),
+        ];
+
+        assert!(!is_neighbourhood_cover(&graph, &clusters, 1));
+        assert!(is_neighbourhood_cover(&graph, &clusters, 0));
+    }
+}
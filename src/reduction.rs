@@ -0,0 +1,380 @@
+use crate::components::induced_subgraph;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, MutableGraph, Vertex, VertexSet};
+use std::cmp::max;
+use std::collections::HashMap;
+
+//Vertices that can be removed before the reduced graph without changing its 2-admissibility.
+//Every vertex r in prefix has at most one neighbour in the reduced graph, or two adjacent ones,
+//so r is never the middle of a 2-path counted by a vertex of the reduced graph.
+//The only exception are degree 2 twins, where one twin is kept in the reduced graph and moved to front
+pub struct Reduction {
+    pub prefix: Vec<Vertex>,
+    pub front: Vec<Vertex>,
+    pub prefix_p: i32, //largest value of a vertex in prefix when it is removed
+}
+
+impl Reduction {
+    //Turns an ordering of the reduced graph into an ordering of the original graph
+    pub fn lift(&self, order: Vec<Vertex>) -> Vec<Vertex> {
+        let front: VertexSet = self.front.iter().copied().collect();
+        let mut lifted = self.prefix.clone();
+        lifted.extend(self.front.iter());
+        lifted.extend(order.into_iter().filter(|v| !front.contains(v)));
+        lifted
+    }
+}
+
+fn neighbours_of(graph: &EditGraph, v: &Vertex) -> Vec<Vertex> {
+    graph.neighbours(v).copied().collect()
+}
+
+//Removes vertices of degree at most 1 until there are none left
+fn remove_pendants(graph: &mut EditGraph, reduction: &mut Reduction) -> bool {
+    let mut queue: Vec<Vertex> = graph
+        .vertices()
+        .filter(|v| graph.degree(v) <= 1)
+        .copied()
+        .collect();
+    let changed = !queue.is_empty();
+
+    while let Some(v) = queue.pop() {
+        if !graph.contains(&v) || graph.degree(&v) > 1 {
+            continue;
+        }
+        let neighbours = neighbours_of(graph, &v);
+        reduction.prefix_p = max(reduction.prefix_p, neighbours.len() as i32);
+        reduction.prefix.push(v);
+        graph.remove_vertex(&v);
+        for u in neighbours {
+            if graph.degree(&u) <= 1 {
+                queue.push(u);
+            }
+        }
+    }
+    changed
+}
+
+//Returns the maximal path of degree 2 vertices through v in order along the path,
+//or the whole cycle if v is on a cycle of degree 2 vertices
+fn degree_2_chain(graph: &EditGraph, v: Vertex) -> Vec<Vertex> {
+    let mut sides = Vec::new();
+    for start in neighbours_of(graph, &v) {
+        let mut side = Vec::new();
+        let mut previous = v;
+        let mut current = start;
+        while graph.degree(&current) == 2 && current != v {
+            side.push(current);
+            let next = neighbours_of(graph, &current)
+                .into_iter()
+                .find(|u| *u != previous)
+                .unwrap();
+            previous = current;
+            current = next;
+        }
+        if current == v {
+            //Went round a cycle of degree 2 vertices
+            side.insert(0, v);
+            return side;
+        }
+        sides.push(side);
+    }
+    let mut chain: Vec<Vertex> = sides[0].iter().rev().copied().collect();
+    chain.push(v);
+    chain.extend(sides[1].iter());
+    chain
+}
+
+//Removes degree 2 vertices whose neighbours are adjacent and chains of at least two degree 2 vertices.
+//Only called once there are no vertices of degree at most 1 so the graph has a cycle and p is at least 2
+fn remove_degree_2(graph: &mut EditGraph, reduction: &mut Reduction) -> bool {
+    let mut changed = false;
+    let degree_2: Vec<Vertex> = graph
+        .vertices()
+        .filter(|v| graph.degree(v) == 2)
+        .copied()
+        .collect();
+
+    for v in degree_2 {
+        if !graph.contains(&v) || graph.degree(&v) != 2 {
+            continue;
+        }
+        let neighbours = neighbours_of(graph, &v);
+        let removed = if graph.adjacent(&neighbours[0], &neighbours[1]) {
+            vec![v]
+        } else {
+            let chain = degree_2_chain(graph, v);
+            if chain.len() < 2 {
+                continue;
+            }
+            chain
+        };
+
+        //Removing the chain in order along it gives each vertex at most one neighbour in L
+        //and at most one path through the previous vertex of the chain
+        for u in removed {
+            reduction.prefix.push(u);
+            graph.remove_vertex(&u);
+        }
+        reduction.prefix_p = max(reduction.prefix_p, 2);
+        changed = true;
+    }
+    changed
+}
+
+//Degree 2 vertices with the same non adjacent neighbours a and b only give a and b a single
+//2-path between each other when they are removed first, so all but one twin are removed and
+//the twin that is kept is moved to the front of the ordering of the reduced graph
+fn remove_degree_2_twins(graph: &mut EditGraph, reduction: &mut Reduction) {
+    let mut twins: HashMap<(Vertex, Vertex), Vec<Vertex>> = HashMap::new();
+    for v in graph.vertices() {
+        if graph.degree(v) == 2 {
+            let mut neighbours = neighbours_of(graph, v);
+            neighbours.sort();
+            twins.entry((neighbours[0], neighbours[1])).or_default().push(*v);
+        }
+    }
+
+    for ((a, b), mut same) in twins {
+        if same.len() < 2 || graph.adjacent(&a, &b) {
+            continue;
+        }
+        same.sort();
+        reduction.front.push(same[0]);
+        for v in &same[1..] {
+            reduction.prefix.push(*v);
+            graph.remove_vertex(v);
+        }
+        reduction.prefix_p = max(reduction.prefix_p, 2);
+    }
+}
+
+//Shrinks graph so that p of graph is the max of prefix_p and p of the reduced graph,
+//and any ordering of the reduced graph can be lifted to an ordering of graph with that p
+pub fn reduce(graph: &EditGraph) -> (EditGraph, Reduction) {
+    let all_vertices: VertexSet = graph.vertices().copied().collect();
+    let mut reduced = induced_subgraph(graph, &all_vertices);
+    let mut reduction = Reduction {
+        prefix: Vec::new(),
+        front: Vec::new(),
+        prefix_p: 0,
+    };
+
+    loop {
+        remove_pendants(&mut reduced, &mut reduction);
+        if !remove_degree_2(&mut reduced, &mut reduction) {
+            break;
+        }
+    }
+    remove_degree_2_twins(&mut reduced, &mut reduction);
+
+    (reduced, reduction)
+}
+
+#[cfg(test)]
+mod test_reduction {
+    use crate::checkOrdering::ordering_p;
+    use crate::compute_ordering;
+    use crate::generators::graph_from_edges;
+    use crate::reduction::{degree_2_chain, reduce};
+    use graphbench::editgraph::EditGraph;
+    use graphbench::graph::Graph;
+    use std::cmp::max;
+
+    fn smallest_p(graph: &EditGraph) -> usize {
+        let mut p = 0;
+        while compute_ordering(p, graph, false).is_none() {
+            p += 1;
+        }
+        p
+    }
+
+    //The reduction is safe if p of the graph is the max of prefix_p and p of the reduced graph,
+    //and the lifted ordering of the reduced graph attains that p
+    fn assert_reduction_is_safe(graph: &EditGraph) {
+        let (reduced, reduction) = reduce(graph);
+        let reduced_p = smallest_p(&reduced);
+        let p = max(reduced_p as i32, reduction.prefix_p) as usize;
+
+        assert_eq!(p, smallest_p(graph));
+
+        let order = compute_ordering(reduced_p, &reduced, true).unwrap();
+        let lifted = reduction.lift(order);
+        assert_eq!(lifted.len(), graph.num_vertices());
+        assert_eq!(ordering_p(graph, &lifted), p);
+    }
+
+    #[test]
+    fn reduce_should_remove_trees() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (2, 5), (2, 6), (3, 7)]);
+
+        let (reduced, reduction) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 0);
+        assert_eq!(reduction.prefix.len(), 7);
+        assert_eq!(reduction.prefix_p, 1);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_remove_trees_hanging_from_k4() {
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (1, 5),
+            (5, 6),
+            (5, 7),
+            (2, 8),
+        ]);
+
+        let (reduced, _) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 4);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_remove_degree_2_vertices_with_adjacent_neighbours() {
+        //Triangles 1,2,5 and 3,4,6 on a K4
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (1, 5),
+            (2, 5),
+            (3, 6),
+            (4, 6),
+        ]);
+
+        let (reduced, reduction) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 4);
+        assert_eq!(reduction.prefix_p, 2);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_keep_single_degree_2_vertex_between_non_adjacent_vertices() {
+        //5 gives 1 and 2 a 2-path between each other, removing it would lower p
+        let graph = graph_from_edges(&[
+            (1, 3),
+            (1, 4),
+            (1, 6),
+            (2, 3),
+            (2, 4),
+            (2, 6),
+            (3, 4),
+            (3, 6),
+            (4, 6),
+            (1, 5),
+            (2, 5),
+        ]);
+
+        let (reduced, _) = reduce(&graph);
+
+        assert!(reduced.contains(&5));
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_remove_chains_of_degree_2_vertices() {
+        //Path 5,6,7 between 1 and 2 and path 8,9 between 3 and 3 on a K4
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (1, 5),
+            (5, 6),
+            (6, 7),
+            (7, 2),
+            (3, 8),
+            (8, 9),
+            (9, 3),
+        ]);
+
+        let (reduced, reduction) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 4);
+        assert_eq!(reduction.prefix.len(), 5);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_remove_cycles() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (5, 1)]);
+
+        let (reduced, reduction) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 0);
+        assert_eq!(reduction.prefix_p, 2);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_keep_one_of_each_group_of_degree_2_twins() {
+        //K_{2,4} with 5,6,7,8 twins between 1 and 2, and a K4 on 1,2,3,4 without edge 1,2
+        let graph = graph_from_edges(&[
+            (1, 5),
+            (1, 6),
+            (1, 7),
+            (1, 8),
+            (2, 5),
+            (2, 6),
+            (2, 7),
+            (2, 8),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+        ]);
+
+        let (reduced, reduction) = reduce(&graph);
+
+        assert_eq!(reduced.num_vertices(), 5);
+        assert_eq!(reduction.front, vec![5]);
+        assert_eq!(reduction.prefix, vec![6, 7, 8]);
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn reduce_should_be_safe_on_graph_without_reductions() {
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 9),
+            (2, 3),
+            (2, 9),
+            (3, 4),
+            (3, 7),
+            (3, 9),
+            (4, 5),
+            (4, 6),
+            (5, 6),
+            (5, 8),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+        ]);
+
+        assert_reduction_is_safe(&graph);
+    }
+
+    #[test]
+    fn degree_2_chain_should_return_chain_in_order() {
+        let graph = graph_from_edges(&[(1, 2), (2, 3), (3, 4), (4, 5), (1, 6), (1, 7), (5, 8), (5, 9)]);
+
+        let chain = degree_2_chain(&graph, 3);
+
+        assert!(chain == vec![2, 3, 4] || chain == vec![4, 3, 2]);
+    }
+}
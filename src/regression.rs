@@ -0,0 +1,242 @@
+use crate::admGraph::AdmOptions;
+use crate::{find_p, find_p_by_component, load_graph};
+use graphbench::graph::{Graph, MutableGraph};
+use std::fmt::Write;
+use std::io::Read;
+use std::path::Path;
+use std::time::Instant;
+
+//A row of results.csv, the values are written as =<value> so they are read as text by spreadsheets
+#[derive(Clone)]
+pub struct ExpectedResult {
+    pub network: String,
+    pub p: i32,
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    pub memory_kb: f64,
+    pub memory_load_kb: f64,
+}
+
+pub struct ActualResult {
+    pub p: i32,
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    pub memory_kb: Option<f64>,
+    pub memory_load_kb: Option<f64>,
+    pub time_ms: f64,
+}
+
+//Resets and reads the peak memory of the allocator of the binary, which the library does not set
+pub struct PeakMemory {
+    pub reset: fn(),
+    pub peak_kb: fn() -> f64,
+}
+
+pub struct Comparison {
+    pub network: String,
+    pub expected: Option<ExpectedResult>,
+    pub actual: ActualResult,
+}
+
+fn value<'a>(headers: &csv::StringRecord, record: &'a csv::StringRecord, column: &str) -> Result<&'a str, String> {
+    let index = headers
+        .iter()
+        .position(|h| h == column)
+        .ok_or_else(|| format!("Missing column {}", column))?;
+    let field = record.get(index).unwrap_or("");
+    Ok(field.strip_prefix('=').unwrap_or(field))
+}
+
+fn parse<T: std::str::FromStr>(
+    headers: &csv::StringRecord,
+    record: &csv::StringRecord,
+    column: &str,
+) -> Result<T, String> {
+    let field = value(headers, record, column)?;
+    field
+        .parse()
+        .map_err(|_| format!("Invalid {} {} for {}", column, field, &record[0]))
+}
+
+pub fn read_expected<R: Read>(reader: R) -> Result<Vec<ExpectedResult>, String> {
+    let mut reader = csv::ReaderBuilder::new().delimiter(b';').from_reader(reader);
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let mut expected = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        expected.push(ExpectedResult {
+            network: value(&headers, &record, "Network")?.to_string(),
+            p: parse(&headers, &record, "admissibility_2")?,
+            num_vertices: parse(&headers, &record, "num_vertices")?,
+            num_edges: parse(&headers, &record, "num_edges")?,
+            memory_kb: parse(&headers, &record, "memory")?,
+            memory_load_kb: parse(&headers, &record, "memoryLoad")?,
+        });
+    }
+    Ok(expected)
+}
+
+pub fn load_expected(csv_path: &str) -> Result<Vec<ExpectedResult>, String> {
+    let file = std::fs::File::open(csv_path).map_err(|e| format!("{}: {}", csv_path, e))?;
+    read_expected(file)
+}
+
+//Networks of the CSV that have a file in network_path, so a partial copy of the corpus can be checked
+pub fn available_networks(expected: &[ExpectedResult], network_path: &str) -> Vec<String> {
+    expected
+        .iter()
+        .map(|e| e.network.clone())
+        .filter(|network| Path::new(&format!("{}/{}.txt.gz", network_path, network)).exists())
+        .collect()
+}
+
+//Loads and solves network the same way as a run without a command, memory is only measured when
+//given as the peak is shared with anything allocated before
+pub fn rerun(
+    network_path: &str,
+    network: &str,
+    start_p: i32,
+    components: bool,
+    memory: Option<&PeakMemory>,
+    options: &AdmOptions,
+) -> ActualResult {
+    if let Some(memory) = memory {
+        (memory.reset)();
+    }
+    let start = Instant::now();
+    let (mut graph, _) = load_graph(network_path.to_string(), &network.to_string());
+    graph.remove_loops();
+    let memory_load_kb = memory.map(|memory| (memory.peak_kb)());
+    let p = if components {
+        find_p_by_component(start_p, &graph, false, options, &mut Vec::new()).0
+    } else {
+        find_p(start_p, &graph, false, options, &mut Vec::new()).0
+    };
+    ActualResult {
+        p,
+        num_vertices: graph.num_vertices(),
+        num_edges: graph.num_edges(),
+        memory_kb: memory.map(|memory| (memory.peak_kb)()),
+        memory_load_kb,
+        time_ms: start.elapsed().as_secs_f64() * 1000.0,
+    }
+}
+
+impl Comparison {
+    pub fn mismatches(&self) -> Vec<String> {
+        let Some(expected) = &self.expected else {
+            return vec!["not in the results".to_string()];
+        };
+        let mut mismatches = Vec::new();
+        if self.actual.p != expected.p {
+            mismatches.push(format!("p is {} instead of {}", self.actual.p, expected.p));
+        }
+        if self.actual.num_vertices != expected.num_vertices {
+            mismatches.push(format!(
+                "{} vertices instead of {}",
+                self.actual.num_vertices, expected.num_vertices
+            ));
+        }
+        if self.actual.num_edges != expected.num_edges {
+            mismatches.push(format!("{} edges instead of {}", self.actual.num_edges, expected.num_edges));
+        }
+        mismatches
+    }
+}
+
+//results.csv has no times, so only the time of the rerun is reported
+pub fn to_text(comparisons: &[Comparison]) -> String {
+    let mut text = String::new();
+    for comparison in comparisons {
+        let mismatches = comparison.mismatches();
+        let status = if mismatches.is_empty() {
+            "ok".to_string()
+        } else {
+            format!("MISMATCH {}", mismatches.join(", "))
+        };
+        write!(
+            text,
+            "{}: {} with p = {} in {:.3} ms",
+            comparison.network, status, comparison.actual.p, comparison.actual.time_ms
+        )
+        .unwrap();
+        if let (Some(expected), Some(memory), Some(memory_load)) = (
+            &comparison.expected,
+            comparison.actual.memory_kb,
+            comparison.actual.memory_load_kb,
+        ) {
+            write!(
+                text,
+                ", memory {:+.1} kb and memory after loading {:+.1} kb from the results",
+                memory - expected.memory_kb,
+                memory_load - expected.memory_load_kb
+            )
+            .unwrap();
+        }
+        writeln!(text).unwrap();
+    }
+    let failed = comparisons.iter().filter(|c| !c.mismatches().is_empty()).count();
+    writeln!(text, "{} of {} networks match the results", comparisons.len() - failed, comparisons.len()).unwrap();
+    text
+}
+
+#[cfg(test)]
+mod test_regression {
+    use crate::admGraph::AdmOptions;
+    use crate::regression::{
+        available_networks, load_expected, read_expected, rerun, to_text, ActualResult, Comparison,
+    };
+
+    const RESULTS: &str = "Network;admissibility_2;avg_degree;degen_lower;degen_upper;max_degree;memory;memoryLoad;min_degree;num_edges;num_vertices\n\
+        a;=2;=2.0;=2;=2;=2;=10.5;=4.25;=2;=3;=3\n\
+        b;=3;=3.0;=3;=3;=3;=20;=8;=3;=6;=4\n";
+
+    fn actual(p: i32, num_vertices: usize, num_edges: usize) -> ActualResult {
+        ActualResult {
+            p,
+            num_vertices,
+            num_edges,
+            memory_kb: Some(12.0),
+            memory_load_kb: Some(4.0),
+            time_ms: 1.0,
+        }
+    }
+
+    #[test]
+    fn comparison_should_report_mismatched_values() {
+        let mut expected = read_expected(RESULTS.as_bytes()).unwrap();
+        let b = expected.pop().unwrap();
+        let a = expected.pop().unwrap();
+        assert_eq!((a.network.as_str(), a.p, a.num_vertices, a.num_edges), ("a", 2, 3, 3));
+        assert_eq!((a.memory_kb, a.memory_load_kb), (10.5, 4.25));
+
+        let comparisons = [
+            Comparison { network: "a".to_string(), expected: Some(a), actual: actual(2, 3, 3) },
+            Comparison { network: "b".to_string(), expected: Some(b), actual: actual(2, 4, 5) },
+            Comparison { network: "c".to_string(), expected: None, actual: actual(1, 2, 1) },
+        ];
+
+        assert!(comparisons[0].mismatches().is_empty());
+        assert_eq!(comparisons[1].mismatches(), ["p is 2 instead of 3", "5 edges instead of 6"]);
+        assert_eq!(comparisons[2].mismatches(), ["not in the results"]);
+        let text = to_text(&comparisons);
+        assert!(text.starts_with(
+            "a: ok with p = 2 in 1.000 ms, memory +1.5 kb and memory after loading -0.2 kb from the results\n"
+        ));
+        assert!(text.ends_with("1 of 3 networks match the results\n"));
+    }
+
+    #[test]
+    fn rerun_should_match_results_for_bundled_networks() {
+        let expected = load_expected("results/results.csv").unwrap();
+        let networks = available_networks(&expected, "networks");
+        assert!(networks.contains(&"karate".to_string()));
+
+        for network in networks {
+            let actual = rerun("networks", &network, 1, false, None, &AdmOptions::default());
+            let expected = expected.iter().find(|e| e.network == network).cloned();
+            let comparison = Comparison { network, expected, actual };
+            assert!(comparison.mismatches().is_empty(), "{}", to_text(&[comparison]));
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::admGraph::{AdmStats, Discrepancy};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::Write;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+//A single p value checked during the search
+#[derive(Serialize, Clone, Debug)]
+pub struct Probe {
+    pub p: usize,
+    pub is_p: bool,
+    pub time_ms: f64,
+    pub stats: AdmStats,
+    #[serde(skip)]
+    pub discrepancies: Vec<Discrepancy>, //only found with --exact-check, printed to stderr with vertex labels
+    pub component: Option<usize>, //index of the component checked with --components, largest first
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Memory {
+    pub load_kb: f32,  //peak memory after the graph is loaded
+    pub total_kb: f32, //peak memory over the whole run
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ComponentSummary {
+    pub count: usize,
+    pub solved: usize, //components that needed the p search
+    pub max_vertex: Option<String>, //smallest vertex of the component attaining p
+    pub max_vertices: usize,
+    pub max_edges: usize,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ReductionSummary {
+    pub removed: usize,
+    pub prefix_p: i32, //largest value of a removed vertex
+    pub reduced_vertices: usize,
+    pub reduced_edges: usize,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LowerBoundSummary {
+    pub degeneracy: usize,
+    pub stalled_set: Option<usize>, //None if p is 0, so there is no elimination that stalls
+    pub best: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RunResult {
+    pub network: String,
+    pub p: i32,
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    pub history: Vec<Probe>,
+    pub load_time_ms: f64,
+    pub total_time_ms: f64,
+    pub memory: Option<Memory>,
+    pub components: Option<ComponentSummary>,
+    pub reduction: Option<ReductionSummary>,
+    pub lower_bound: Option<LowerBoundSummary>,
+    pub ordering: Option<Vec<String>>, //original vertex labels
+}
+
+//Flattened version of RunResult used for the csv output
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    network: &'a str,
+    p: i32,
+    num_vertices: usize,
+    num_edges: usize,
+    history: String,
+    load_time_ms: f64,
+    total_time_ms: f64,
+    memory_load_kb: Option<f32>,
+    memory_total_kb: Option<f32>,
+    components: Option<usize>,
+    reduced_vertices: Option<usize>,
+    lower_bound: Option<usize>,
+    ordering: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CsvOrderingRow<'a> {
+    position: usize,
+    vertex: &'a str,
+}
+
+impl RunResult {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(memory) = &self.memory {
+            writeln!(text, "Max memory used after graph loading in kb is {}", memory.load_kb).unwrap();
+        }
+        for probe in &self.history {
+            let component = probe.component.map_or(String::new(), |c| format!(" on component {}", c));
+            writeln!(text, "p = {} is p: {}{} in {:.3} ms", probe.p, probe.is_p, component, probe.time_ms).unwrap();
+            let stats = &probe.stats;
+            writeln!(
+                text,
+                "    {} do_checks, {} of {} searches successful with {:.1} auxiliary graph vertices on average, {} update_m, peak candidates {}",
+                stats.do_checks,
+                stats.successful_searches,
+                stats.searches,
+                stats.average_aux_vertices(),
+                stats.update_m,
+                stats.peak_candidates
+            )
+            .unwrap();
+            writeln!(
+                text,
+                "    n1 repair added {}, failed {} and skipped {}, l2 repair replaced {}, failed {} and skipped {}, {} edges added without a search",
+                stats.n1_added,
+                stats.n1_failed,
+                stats.n1_skipped,
+                stats.l2_replaced,
+                stats.l2_failed,
+                stats.l2_skipped,
+                stats.unmatched_edges
+            )
+            .unwrap();
+        }
+        writeln!(text, "p is {}", self.p).unwrap();
+        if let Some(reduction) = &self.reduction {
+            writeln!(
+                text,
+                "Reductions removed {} vertices leaving {} vertices and {} edges",
+                reduction.removed, reduction.reduced_vertices, reduction.reduced_edges
+            )
+            .unwrap();
+        }
+        if let Some(components) = &self.components {
+            writeln!(
+                text,
+                "Graph has {} components of which {} needed the p search",
+                components.count, components.solved
+            )
+            .unwrap();
+            if let Some(v) = &components.max_vertex {
+                writeln!(
+                    text,
+                    "p is attained by the component of vertex {} with {} vertices and {} edges",
+                    v, components.max_vertices, components.max_edges
+                )
+                .unwrap();
+            }
+        }
+        if let Some(lower_bound) = &self.lower_bound {
+            let stalled_set = lower_bound.stalled_set.map_or("-".to_string(), |b| b.to_string());
+            writeln!(
+                text,
+                "Lower bound is {} from degeneracy {} and stalled set {}",
+                lower_bound.best, lower_bound.degeneracy, stalled_set
+            )
+            .unwrap();
+            if (lower_bound.best as i32) < self.p {
+                writeln!(text, "Gap of {} between lower bound and p", self.p - lower_bound.best as i32).unwrap();
+            }
+        }
+        writeln!(text, "Graph loaded in {:.3} ms, total time {:.3} ms", self.load_time_ms, self.total_time_ms).unwrap();
+        if let Some(memory) = &self.memory {
+            writeln!(text, "Max memory used in total kb is {}", memory.total_kb).unwrap();
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    //Single row with the search history encoded as p:is_p pairs, prefixed by component@ when solving
+    //components separately, and the ordering space separated
+    pub fn to_csv(&self) -> String {
+        let history = self
+            .history
+            .iter()
+            .map(|probe| match probe.component {
+                Some(c) => format!("{}@{}:{}", c, probe.p, probe.is_p as u8),
+                None => format!("{}:{}", probe.p, probe.is_p as u8),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let ordering = self.ordering.as_ref().map(|order| order.join(" "));
+        let row = CsvRow {
+            network: &self.network,
+            p: self.p,
+            num_vertices: self.num_vertices,
+            num_edges: self.num_edges,
+            history,
+            load_time_ms: self.load_time_ms,
+            total_time_ms: self.total_time_ms,
+            memory_load_kb: self.memory.as_ref().map(|m| m.load_kb),
+            memory_total_kb: self.memory.as_ref().map(|m| m.total_kb),
+            components: self.components.as_ref().map(|c| c.count),
+            reduced_vertices: self.reduction.as_ref().map(|r| r.reduced_vertices),
+            lower_bound: self.lower_bound.as_ref().map(|l| l.best),
+            ordering,
+        };
+
+        let mut writer = csv::WriterBuilder::new().delimiter(b';').from_writer(vec![]);
+        writer.serialize(row).unwrap();
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    pub fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_text(),
+            OutputFormat::Json => self.to_json(),
+            OutputFormat::Csv => self.to_csv(),
+        }
+    }
+}
+
+//Ordering as a csv table of position;vertex
+pub fn ordering_to_csv(order: &[String]) -> String {
+    let mut writer = csv::WriterBuilder::new().delimiter(b';').from_writer(vec![]);
+    for (position, v) in order.iter().enumerate() {
+        writer.serialize(CsvOrderingRow { position, vertex: v }).unwrap();
+    }
+    String::from_utf8(writer.into_inner().unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod test_run_result {
+    use crate::admGraph::AdmStats;
+    use crate::runResult::{
+        ordering_to_csv, ComponentSummary, LowerBoundSummary, Memory, Probe, ReductionSummary,
+        RunResult,
+    };
+
+    fn stats() -> AdmStats {
+        AdmStats {
+            do_checks: 3,
+            searches: 4,
+            successful_searches: 1,
+            aux_vertices: 10,
+            update_m: 1,
+            peak_candidates: 2,
+            ..AdmStats::default()
+        }
+    }
+
+    fn run_result() -> RunResult {
+        RunResult {
+            network: "windsurfers".to_string(),
+            p: 5,
+            num_vertices: 3,
+            num_edges: 2,
+            history: vec![
+                Probe { p: 11, is_p: true, time_ms: 1.5, stats: AdmStats::default(), discrepancies: Vec::new(), component: None },
+                Probe { p: 5, is_p: true, time_ms: 1.0, stats: AdmStats::default(), discrepancies: Vec::new(), component: None },
+                Probe { p: 4, is_p: false, time_ms: 0.5, stats: stats(), discrepancies: Vec::new(), component: Some(1) },
+            ],
+            load_time_ms: 2.0,
+            total_time_ms: 5.0,
+            memory: Some(Memory { load_kb: 10.0, total_kb: 20.0 }),
+            components: Some(ComponentSummary {
+                count: 2,
+                solved: 1,
+                max_vertex: Some("a".to_string()),
+                max_vertices: 2,
+                max_edges: 1,
+            }),
+            reduction: Some(ReductionSummary {
+                removed: 1,
+                prefix_p: 1,
+                reduced_vertices: 2,
+                reduced_edges: 1,
+            }),
+            lower_bound: Some(LowerBoundSummary {
+                degeneracy: 1,
+                stalled_set: Some(4),
+                best: 4,
+            }),
+            ordering: Some(vec!["3".to_string(), "a".to_string(), "2".to_string()]),
+        }
+    }
+
+    //Documents the json schema of a run:
+    //{
+    //  "network": string,
+    //  "p": integer,
+    //  "num_vertices": integer,
+    //  "num_edges": integer,
+    //  "history": [{"p": integer, "is_p": bool, "time_ms": number,
+    //               "stats": {"n1_added": integer, "n1_failed": integer, "n1_skipped": integer,
+    //                         "l2_replaced": integer, "l2_failed": integer, "l2_skipped": integer,
+    //                         "do_checks": integer, "unmatched_edges": integer, "searches": integer,
+    //                         "successful_searches": integer, "aux_vertices": integer,
+    //                         "update_m": integer, "peak_candidates": integer},
+    //               "component": null | integer}],
+    //  "load_time_ms": number,
+    //  "total_time_ms": number,
+    //  "memory": null | {"load_kb": number, "total_kb": number},
+    //  "components": null | {"count": integer, "solved": integer, "max_vertex": null | string,
+    //                        "max_vertices": integer, "max_edges": integer},
+    //  "reduction": null | {"removed": integer, "prefix_p": integer, "reduced_vertices": integer,
+    //                       "reduced_edges": integer},
+    //  "lower_bound": null | {"degeneracy": integer, "stalled_set": null | integer, "best": integer},
+    //  "ordering": null | [string]
+    //}
+    #[test]
+    fn to_json_should_follow_documented_schema() {
+        let json: serde_json::Value = serde_json::from_str(&run_result().to_json()).unwrap();
+
+        assert_eq!(json.as_object().unwrap().len(), 12);
+        assert_eq!(json["network"], "windsurfers");
+        assert_eq!(json["p"], 5);
+        assert_eq!(json["num_vertices"], 3);
+        assert_eq!(json["num_edges"], 2);
+        let history = json["history"].as_array().unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2]["p"], 4);
+        assert_eq!(history[2]["is_p"], false);
+        assert!(history[2]["time_ms"].is_f64());
+        assert_eq!(history[2]["stats"].as_object().unwrap().len(), 13);
+        assert_eq!(history[2]["stats"]["searches"], 4);
+        assert_eq!(history[2]["stats"]["peak_candidates"], 2);
+        assert!(history[0]["component"].is_null());
+        assert_eq!(history[2]["component"], 1);
+        assert!(json["load_time_ms"].is_f64());
+        assert!(json["total_time_ms"].is_f64());
+        assert!(json["memory"]["load_kb"].is_f64());
+        assert!(json["memory"]["total_kb"].is_f64());
+        assert_eq!(json["components"]["count"], 2);
+        assert_eq!(json["components"]["solved"], 1);
+        assert_eq!(json["components"]["max_vertex"], "a");
+        assert_eq!(json["components"]["max_vertices"], 2);
+        assert_eq!(json["components"]["max_edges"], 1);
+        assert_eq!(json["reduction"]["removed"], 1);
+        assert_eq!(json["reduction"]["prefix_p"], 1);
+        assert_eq!(json["reduction"]["reduced_vertices"], 2);
+        assert_eq!(json["reduction"]["reduced_edges"], 1);
+        assert_eq!(json["lower_bound"]["stalled_set"], 4);
+        assert_eq!(json["lower_bound"]["best"], 4);
+        assert_eq!(json["ordering"], serde_json::json!(["3", "a", "2"]));
+    }
+
+    #[test]
+    fn to_json_should_use_null_for_missing_optional_fields() {
+        let mut result = run_result();
+        result.memory = None;
+        result.components = None;
+        result.reduction = None;
+        result.lower_bound = None;
+        result.ordering = None;
+
+        let json: serde_json::Value = serde_json::from_str(&result.to_json()).unwrap();
+
+        assert!(json["memory"].is_null());
+        assert!(json["components"].is_null());
+        assert!(json["reduction"].is_null());
+        assert!(json["lower_bound"].is_null());
+        assert!(json["ordering"].is_null());
+    }
+
+    #[test]
+    fn to_csv_should_write_header_and_single_row() {
+        let csv = run_result().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "network;p;num_vertices;num_edges;history;load_time_ms;total_time_ms;memory_load_kb;memory_total_kb;components;reduced_vertices;lower_bound;ordering"
+        );
+        assert!(lines[1].starts_with("windsurfers;5;3;2;11:1 5:1 1@4:0;"));
+        assert!(lines[1].ends_with(";2;2;4;3 a 2"));
+    }
+
+    #[test]
+    fn to_text_should_report_p() {
+        let text = run_result().to_text();
+
+        assert!(text.contains("p is 5\n"));
+        assert!(text.contains("1 of 4 searches successful with 2.5 auxiliary graph vertices"));
+        assert!(text.contains("Max memory used in total kb is 20"));
+        assert!(text.contains("Lower bound is 4 from degeneracy 1 and stalled set 4\n"));
+        assert!(text.contains("Gap of 1 between lower bound and p\n"));
+    }
+
+    #[test]
+    fn ordering_to_csv_should_write_position_of_each_vertex() {
+        let csv = ordering_to_csv(&["3".to_string(), "a".to_string(), "2".to_string()]);
+
+        assert_eq!(csv, "position;vertex\n0;3\n1;a\n2;2\n");
+    }
+}
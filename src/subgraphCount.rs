@@ -0,0 +1,136 @@
+use crate::checkOrdering::positions;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap, VertexSet};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubgraphCounts {
+    pub triangles: u64,
+    pub four_cycles: u64,
+    pub two_paths: u64,
+}
+
+//Baseline ordering by increasing degree, ties broken by vertex
+pub fn degree_ordering(graph: &EditGraph) -> Vec<Vertex> {
+    let mut order: Vec<Vertex> = graph.vertices().copied().collect();
+    order.sort_by_key(|v| (graph.degree(v), *v));
+    order
+}
+
+//Each edge points to the endpoint that comes later in order. For the order from compute_ordering these
+//are the neighbours in L, so every vertex has at most p out-neighbours
+fn orient(graph: &EditGraph, positions: &VertexMap<usize>) -> VertexMap<Vec<Vertex>> {
+    graph
+        .vertices()
+        .map(|v| {
+            let out = graph
+                .neighbours(v)
+                .filter(|u| positions[u] > positions[v])
+                .copied()
+                .collect();
+            (*v, out)
+        })
+        .collect()
+}
+
+//Each triangle is found once from its first vertex in order, through out-neighbours only. With at
+//most p out-neighbours per vertex this takes O(p * m) time
+fn count_triangles(out: &VertexMap<Vec<Vertex>>) -> u64 {
+    let mut triangles = 0;
+    for (_, v_out) in out.iter() {
+        let marked: VertexSet = v_out.iter().copied().collect();
+        for u in v_out {
+            triangles += out[u].iter().filter(|w| marked.contains(w)).count() as u64;
+        }
+    }
+    triangles
+}
+
+//Each 4-cycle v-u-w-x is found once from its last vertex v in order, by counting the 2-paths v-u-w where
+//u and w come before v and pairing those that end in the same w. A vertex u is scanned once for each of
+//its out-neighbours so this takes O(p * m) time for the order from compute_ordering
+fn count_four_cycles(graph: &EditGraph, positions: &VertexMap<usize>) -> u64 {
+    let mut four_cycles = 0;
+    for v in graph.vertices() {
+        let mut ends: VertexMap<u64> = VertexMap::default();
+        for u in graph.neighbours(v).filter(|u| positions[u] < positions[v]) {
+            for w in graph.neighbours(u).filter(|w| positions[w] < positions[v]) {
+                *ends.entry(*w).or_insert(0) += 1;
+            }
+        }
+        four_cycles += ends.values().map(|c| c * (c - 1) / 2).sum::<u64>();
+    }
+    four_cycles
+}
+
+fn count_two_paths(graph: &EditGraph) -> u64 {
+    graph
+        .vertices()
+        .map(|v| {
+            let d = graph.degree(v) as u64;
+            d * d.saturating_sub(1) / 2
+        })
+        .sum()
+}
+
+//Counts triangles, 4-cycles and paths of length 2 (not necessarily induced) with the edges oriented by
+//order, which contains every vertex of graph
+pub fn count_subgraphs(graph: &EditGraph, order: &[Vertex]) -> SubgraphCounts {
+    let positions = positions(order);
+    let out = orient(graph, &positions);
+    SubgraphCounts {
+        triangles: count_triangles(&out),
+        four_cycles: count_four_cycles(graph, &positions),
+        two_paths: count_two_paths(graph),
+    }
+}
+
+#[cfg(test)]
+mod test_subgraph_count {
+    use crate::compute_ordering;
+    use crate::generators::graph_from_edges;
+    use crate::subgraphCount::{count_subgraphs, degree_ordering, SubgraphCounts};
+
+    #[test]
+    fn count_subgraphs_should_count_complete_graph() {
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+        let order = compute_ordering(3, &graph, true).unwrap();
+
+        let counts = count_subgraphs(&graph, &order);
+
+        assert_eq!(
+            counts,
+            SubgraphCounts {
+                triangles: 4,
+                four_cycles: 3,
+                two_paths: 12
+            }
+        );
+    }
+
+    #[test]
+    fn count_subgraphs_should_not_depend_on_ordering() {
+        //A 3x3 grid with one diagonal has 2 triangles, and the diagonal is in no 4-cycle
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (2, 3),
+            (4, 5),
+            (5, 6),
+            (7, 8),
+            (8, 9),
+            (1, 4),
+            (4, 7),
+            (2, 5),
+            (5, 8),
+            (3, 6),
+            (6, 9),
+            (1, 5),
+        ]);
+        let order = compute_ordering(3, &graph, true).unwrap();
+
+        let counts = count_subgraphs(&graph, &order);
+
+        assert_eq!(counts, count_subgraphs(&graph, &degree_ordering(&graph)));
+        assert_eq!(counts.triangles, 2);
+        assert_eq!(counts.four_cycles, 4);
+    }
+}
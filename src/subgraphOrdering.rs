@@ -0,0 +1,108 @@
+use crate::checkOrdering::ordering_p;
+use crate::components::induced_subgraph;
+use crate::compute_ordering;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexSet};
+
+pub struct SubgraphOrdering {
+    pub graph: EditGraph,
+    pub order: Vec<Vertex>,
+    pub restricted_p: usize, //p of the ordering restricted to the subgraph
+    pub p: usize,            //p of order, lower than restricted_p if refining found a better ordering
+}
+
+//Keeps the vertices of order that are in vertices, in the same order
+pub fn restrict_ordering(order: &[Vertex], vertices: &VertexSet) -> Vec<Vertex> {
+    order.iter().filter(|v| vertices.contains(v)).copied().collect()
+}
+
+//The restriction of an ordering to an induced subgraph is an ordering of the subgraph with no larger p,
+//as each vertex keeps a subset of its neighbours and 2-paths. With refine_steps above 0, compute_ordering
+//is run on the subgraph for up to refine_steps smaller values of p while it keeps finding an ordering
+pub fn subgraph_ordering(
+    graph: &EditGraph,
+    order: &[Vertex],
+    vertices: &VertexSet,
+    refine_steps: usize,
+) -> SubgraphOrdering {
+    let vertices: VertexSet = vertices.iter().filter(|v| graph.contains(v)).copied().collect();
+    let subgraph = induced_subgraph(graph, &vertices);
+    let mut order = restrict_ordering(order, &vertices);
+    assert_eq!(order.len(), subgraph.num_vertices(), "Ordering is missing vertices of the subgraph");
+
+    let restricted_p = ordering_p(&subgraph, &order);
+    let mut p = restricted_p;
+    for _ in 0..refine_steps {
+        if p == 0 {
+            break;
+        }
+        match compute_ordering(p - 1, &subgraph, true) {
+            Some(refined) => {
+                p = ordering_p(&subgraph, &refined);
+                order = refined;
+            }
+            None => break,
+        }
+    }
+
+    SubgraphOrdering {
+        graph: subgraph,
+        order,
+        restricted_p,
+        p,
+    }
+}
+
+#[cfg(test)]
+mod test_subgraph_ordering {
+    use crate::checkOrdering::ordering_p;
+    use crate::compute_ordering;
+    use crate::generators::graph_from_edges;
+    use crate::subgraphOrdering::{restrict_ordering, subgraph_ordering};
+    use graphbench::graph::{Graph, VertexSet};
+
+    #[test]
+    fn restrict_ordering_should_keep_order_of_vertices() {
+        let vertices: VertexSet = [4, 1, 3].iter().cloned().collect();
+
+        assert_eq!(restrict_ordering(&[5, 3, 2, 4, 1], &vertices), vec![3, 4, 1]);
+    }
+
+    #[test]
+    fn subgraph_ordering_should_not_have_larger_p_than_ordering() {
+        let graph = graph_from_edges(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 1),
+        ]);
+        let order = compute_ordering(3, &graph, true).unwrap();
+        let vertices: VertexSet = [1, 2, 3, 5, 6, 7].iter().cloned().collect();
+
+        let subgraph = subgraph_ordering(&graph, &order, &vertices, 0);
+
+        assert_eq!(subgraph.graph.num_vertices(), 5);
+        assert!(subgraph.restricted_p <= ordering_p(&graph, &order));
+        assert_eq!(subgraph.p, subgraph.restricted_p);
+        assert_eq!(subgraph.p, ordering_p(&subgraph.graph, &subgraph.order));
+    }
+
+    #[test]
+    fn subgraph_ordering_should_refine_restricted_ordering() {
+        //Removing 1 first gives it value 2 in the subgraph, which is a tree with p = 1
+        let graph = graph_from_edges(&[(1, 2), (1, 3), (1, 4), (2, 5), (3, 6), (4, 7)]);
+        let order = vec![1, 2, 3, 4, 5, 6, 7];
+        let vertices: VertexSet = [1, 2, 3, 5].iter().cloned().collect();
+
+        let subgraph = subgraph_ordering(&graph, &order, &vertices, 3);
+
+        assert_eq!(subgraph.restricted_p, 2);
+        assert_eq!(subgraph.p, 1);
+        assert_eq!(ordering_p(&subgraph.graph, &subgraph.order), 1);
+    }
+}
@@ -0,0 +1,322 @@
+use flate2::read::GzDecoder;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, MutableGraph, Vertex};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+
+//Maps the labels used in an input file to compact vertex ids starting at 0
+#[derive(Default, Debug, Clone)]
+pub struct VertexLabels {
+    labels: Vec<String>,
+    ids: HashMap<String, Vertex>,
+}
+
+impl VertexLabels {
+    pub fn new() -> Self {
+        VertexLabels::default()
+    }
+
+    //Returns the id of the label, assigning the next free id if it has not been seen before
+    pub fn id(&mut self, label: &str) -> Vertex {
+        if let Some(v) = self.ids.get(label) {
+            return *v;
+        }
+        let v = self.labels.len() as Vertex;
+        self.labels.push(label.to_string());
+        self.ids.insert(label.to_string(), v);
+        v
+    }
+
+    pub fn get_id(&self, label: &str) -> Option<Vertex> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, v: Vertex) -> &str {
+        self.labels
+            .get(v as usize)
+            .unwrap_or_else(|| panic!("Vertex {v} has no label"))
+    }
+
+    pub fn labels_of(&self, vertices: &[Vertex]) -> Vec<String> {
+        vertices.iter().map(|v| self.label(*v).to_string()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+//Reads an edge list with one edge per line, where vertices can be any whitespace free label
+//Empty lines and lines starting with # or % are skipped and a line with a single label adds an isolated vertex
+pub fn read_labelled_graph<R: Read>(reader: R) -> std::io::Result<(EditGraph, VertexLabels)> {
+    let mut graph = EditGraph::new();
+    let mut labels = VertexLabels::new();
+
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens[..] {
+            [u] => {
+                let u = labels.id(u);
+                graph.add_vertex(&u);
+            }
+            //A third column such as an edge weight is ignored
+            [u, w] | [u, w, _] => {
+                let u = labels.id(u);
+                let w = labels.id(w);
+                graph.add_edge(&u, &w);
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Expected an edge but found {line}"),
+                ));
+            }
+        }
+    }
+    Ok((graph, labels))
+}
+
+pub fn load_labelled_graph(file_dir: &str) -> std::io::Result<(EditGraph, VertexLabels)> {
+    let file = std::fs::File::open(file_dir)?;
+    read_labelled_graph(GzDecoder::new(file))
+}
+
+//Writes the graph as an edge list read by read_labelled_graph, with vertex ids as labels. Each edge is
+//written once and vertices without edges on a line of their own
+pub fn write_graph<W: Write>(graph: &EditGraph, mut writer: W) -> std::io::Result<()> {
+    let mut vertices: Vec<Vertex> = graph.vertices().copied().collect();
+    vertices.sort();
+    for u in vertices {
+        if graph.degree(&u) == 0 {
+            writeln!(writer, "{}", u)?;
+        }
+        let mut neighbours: Vec<Vertex> = graph.neighbours(&u).filter(|w| u < **w).copied().collect();
+        neighbours.sort();
+        for w in neighbours {
+            writeln!(writer, "{} {}", u, w)?;
+        }
+    }
+    Ok(())
+}
+
+//Reads one label per line, such as a saved ordering or a list of vertices, skipping empty lines
+pub fn read_labels<R: Read>(reader: R) -> std::io::Result<Vec<String>> {
+    let mut labels = Vec::new();
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            labels.push(line.to_string());
+        }
+    }
+    Ok(labels)
+}
+
+//Files ending in .gz, such as saved orderings, are decompressed
+pub fn load_labels(file_dir: &str) -> std::io::Result<Vec<String>> {
+    let file = std::fs::File::open(file_dir)?;
+    if file_dir.ends_with(".gz") {
+        read_labels(GzDecoder::new(file))
+    } else {
+        read_labels(file)
+    }
+}
+
+//Reads one edge change per line as + u v for an insertion or - u v for a deletion, skipping empty lines
+pub fn read_edge_changes<R: Read>(reader: R) -> std::io::Result<Vec<(bool, String, String)>> {
+    let mut changes = Vec::new();
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let insert = match fields.as_slice() {
+            [] => continue,
+            ["+", _, _] => true,
+            ["-", _, _] => false,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Expected + u v or - u v but found {}", line.trim()),
+                ))
+            }
+        };
+        changes.push((insert, fields[1].to_string(), fields[2].to_string()));
+    }
+    Ok(changes)
+}
+
+//Files ending in .gz are decompressed
+pub fn load_edge_changes(file_dir: &str) -> std::io::Result<Vec<(bool, String, String)>> {
+    let file = std::fs::File::open(file_dir)?;
+    if file_dir.ends_with(".gz") {
+        read_edge_changes(GzDecoder::new(file))
+    } else {
+        read_edge_changes(file)
+    }
+}
+
+//Reads the vertex column of an ordering saved with --format csv, or the ordering column of a saved run
+pub fn read_csv_ordering<R: Read>(reader: R) -> std::io::Result<Vec<String>> {
+    let mut reader = csv::ReaderBuilder::new().delimiter(b';').from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+    if let Some(index) = headers.iter().position(|h| h == "vertex") {
+        let mut labels = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let label = record.get(index).ok_or_else(|| invalid(format!("Missing vertex in {:?}", record)))?;
+            labels.push(label.to_string());
+        }
+        Ok(labels)
+    } else if let Some(index) = headers.iter().position(|h| h == "ordering") {
+        let record = reader
+            .records()
+            .next()
+            .ok_or_else(|| invalid("Expected a run but found no rows".to_string()))??;
+        let ordering = record.get(index).unwrap_or("");
+        if ordering.is_empty() {
+            return Err(invalid("The run has no ordering".to_string()));
+        }
+        Ok(ordering.split_whitespace().map(|label| label.to_string()).collect())
+    } else {
+        Err(invalid("Expected a vertex or ordering column".to_string()))
+    }
+}
+
+//Reads the ordering of a run saved with --format json
+pub fn read_json_ordering<R: Read>(reader: R) -> std::io::Result<Vec<String>> {
+    let run: serde_json::Value = serde_json::from_reader(reader)?;
+    run["ordering"]
+        .as_array()
+        .and_then(|ordering| ordering.iter().map(|v| v.as_str().map(|l| l.to_string())).collect())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Expected an ordering array of labels"))
+}
+
+//Reads an ordering in any of the formats of the save command, picked by the extension
+pub fn load_ordering(file_dir: &str) -> std::io::Result<Vec<String>> {
+    if file_dir.ends_with(".json") {
+        read_json_ordering(std::fs::File::open(file_dir)?)
+    } else if file_dir.ends_with(".csv") {
+        read_csv_ordering(std::fs::File::open(file_dir)?)
+    } else {
+        load_labels(file_dir)
+    }
+}
+
+#[cfg(test)]
+mod test_vertex_labels {
+    use crate::vertexLabels::{
+        read_csv_ordering, read_edge_changes, read_json_ordering, read_labelled_graph, read_labels, write_graph,
+        VertexLabels,
+    };
+    use graphbench::editgraph::EditGraph;
+    use graphbench::graph::{Graph, MutableGraph};
+
+    #[test]
+    fn id_should_give_compact_ids_in_order_of_first_appearance() {
+        let mut labels = VertexLabels::new();
+
+        assert_eq!(labels.id("1000"), 0);
+        assert_eq!(labels.id("alice"), 1);
+        assert_eq!(labels.id("1000"), 0);
+        assert_eq!(labels.len(), 2);
+    }
+
+    #[test]
+    fn label_should_return_original_label() {
+        let mut labels = VertexLabels::new();
+        labels.id("bob");
+        labels.id("42");
+
+        assert_eq!(labels.label(1), "42");
+        assert_eq!(labels.labels_of(&[1, 0]), vec!["42", "bob"]);
+    }
+
+    #[test]
+    fn read_labelled_graph_should_compact_sparse_and_string_ids() {
+        let input = "# comment\n100 alice\nalice 7\n\n7 100\nbob\n";
+
+        let (graph, labels) = read_labelled_graph(input.as_bytes()).unwrap();
+
+        assert_eq!(graph.num_vertices(), 4);
+        assert_eq!(graph.num_edges(), 3);
+        let alice = labels.get_id("alice").unwrap();
+        let seven = labels.get_id("7").unwrap();
+        assert!(graph.adjacent(&alice, &seven));
+        assert!(graph.vertices().all(|v| (*v as usize) < labels.len()));
+        assert_eq!(graph.degree(&labels.get_id("bob").unwrap()), 0);
+    }
+
+    #[test]
+    fn read_labelled_graph_should_reject_lines_with_too_many_labels() {
+        let input = "1 2 3 4\n";
+
+        assert!(read_labelled_graph(input.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn read_labels_should_return_one_label_per_line() {
+        let input = "alice\n\n 7 \nbob\n";
+
+        assert_eq!(read_labels(input.as_bytes()).unwrap(), vec!["alice", "7", "bob"]);
+    }
+
+    #[test]
+    fn read_csv_ordering_should_read_ordering_table_and_run() {
+        let table = "position;vertex\n0;3\n1;a\n2;2\n";
+        let run = "network;p;ordering\nx;1;3 a 2\n";
+
+        assert_eq!(read_csv_ordering(table.as_bytes()).unwrap(), vec!["3", "a", "2"]);
+        assert_eq!(read_csv_ordering(run.as_bytes()).unwrap(), vec!["3", "a", "2"]);
+        assert!(read_csv_ordering("network;p;ordering\nx;1;\n".as_bytes()).is_err());
+        assert!(read_csv_ordering("position;label\n0;3\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn read_json_ordering_should_read_ordering_of_run() {
+        let run = r#"{"network": "x", "p": 1, "ordering": ["3", "a", "2"]}"#;
+
+        assert_eq!(read_json_ordering(run.as_bytes()).unwrap(), vec!["3", "a", "2"]);
+        assert!(read_json_ordering(r#"{"p": 1, "ordering": null}"#.as_bytes()).is_err());
+        assert!(read_json_ordering("3\na\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn write_graph_should_be_read_back_with_same_edges() {
+        let mut graph = EditGraph::new();
+        graph.add_edge(&2, &0);
+        graph.add_edge(&0, &1);
+        graph.add_vertex(&3);
+        let mut written = Vec::new();
+
+        write_graph(&graph, &mut written).unwrap();
+        let (read, labels) = read_labelled_graph(&written[..]).unwrap();
+
+        assert_eq!(String::from_utf8(written).unwrap(), "0 1\n0 2\n3\n");
+        assert_eq!(read.num_vertices(), 4);
+        assert_eq!(read.num_edges(), 2);
+        assert!(read.adjacent(&labels.get_id("0").unwrap(), &labels.get_id("2").unwrap()));
+    }
+
+    #[test]
+    fn read_edge_changes_should_read_insertions_and_deletions() {
+        let changes = read_edge_changes("+ alice 7\n\n- 7 100\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                (true, "alice".to_string(), "7".to_string()),
+                (false, "7".to_string(), "100".to_string())
+            ]
+        );
+        assert!(read_edge_changes("+ alice\n".as_bytes()).is_err());
+    }
+}
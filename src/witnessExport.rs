@@ -0,0 +1,166 @@
+use crate::checkOrdering::positions;
+use crate::vertexLabels::VertexLabels;
+use clap::ValueEnum;
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Graph, Vertex, VertexMap};
+use std::fmt::Write;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Dot,
+    Graphml,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Dot => "dot",
+            ExportFormat::Graphml => "graphml",
+        }
+    }
+}
+
+//The 2-paths of each vertex are drawn in the colour picked by its position in the ordering
+const COLOURS: [&str; 8] = [
+    "red", "blue", "darkgreen", "orange", "purple", "brown", "magenta", "cyan",
+];
+
+//2-paths v-r-l of each vertex v in the order given by the ordering. The end l of a path from one of
+//the candidates left at the end of the elimination can come before v in the ordering, those are dropped
+fn witness_paths(
+    order: &[Vertex],
+    witnesses: &VertexMap<Vec<(Vertex, Vertex)>>,
+    positions: &VertexMap<usize>,
+) -> Vec<(Vertex, Vertex, Vertex)> {
+    let mut paths = Vec::new();
+    for v in order {
+        for (r, l) in witnesses.get(v).into_iter().flatten() {
+            if positions[l] > positions[v] {
+                paths.push((*v, *r, *l));
+            }
+        }
+    }
+    paths
+}
+
+fn sorted_edges(graph: &EditGraph) -> Vec<(Vertex, Vertex)> {
+    let mut edges: Vec<(Vertex, Vertex)> = graph
+        .vertices()
+        .flat_map(|u| graph.neighbours(u).filter(move |v| u < *v).map(move |v| (*u, *v)))
+        .collect();
+    edges.sort();
+    edges
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(label: &str) -> String {
+    label
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+//Vertices are labelled by their position in the ordering and their label in the input. Every edge of
+//the graph is drawn in grey and each 2-path v-r-l is drawn again in the colour of v, with r pointing
+//to v and l
+pub fn to_dot(
+    graph: &EditGraph,
+    labels: &VertexLabels,
+    order: &[Vertex],
+    witnesses: &VertexMap<Vec<(Vertex, Vertex)>>,
+) -> String {
+    let positions = positions(order);
+    let mut out = String::new();
+    writeln!(out, "digraph witnesses {{").unwrap();
+    for v in order {
+        writeln!(out, "  {} [label=\"{}: {}\"];", v, positions[v], escape_dot(labels.label(*v))).unwrap();
+    }
+    for (u, v) in sorted_edges(graph) {
+        writeln!(out, "  {} -> {} [dir=none, color=grey];", u, v).unwrap();
+    }
+    for (v, r, l) in witness_paths(order, witnesses, &positions) {
+        let colour = COLOURS[positions[&v] % COLOURS.len()];
+        writeln!(out, "  {} -> {} [color={}];", r, v, colour).unwrap();
+        writeln!(out, "  {} -> {} [color={}];", r, l, colour).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+//Vertices have their position and label as data. Edges of the graph have no witness, and each 2-path
+//v-r-l adds the edges r-v and r-l with v as witness and the colour of v
+pub fn to_graphml(
+    graph: &EditGraph,
+    labels: &VertexLabels,
+    order: &[Vertex],
+    witnesses: &VertexMap<Vec<(Vertex, Vertex)>>,
+) -> String {
+    let positions = positions(order);
+    let mut out = String::new();
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(out, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">").unwrap();
+    writeln!(out, "  <key id=\"position\" for=\"node\" attr.name=\"position\" attr.type=\"int\"/>").unwrap();
+    writeln!(out, "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>").unwrap();
+    writeln!(out, "  <key id=\"witness\" for=\"edge\" attr.name=\"witness\" attr.type=\"int\"/>").unwrap();
+    writeln!(out, "  <key id=\"colour\" for=\"edge\" attr.name=\"colour\" attr.type=\"string\"/>").unwrap();
+    writeln!(out, "  <graph id=\"witnesses\" edgedefault=\"undirected\">").unwrap();
+    for v in order {
+        writeln!(out, "    <node id=\"{}\">", v).unwrap();
+        writeln!(out, "      <data key=\"position\">{}</data>", positions[v]).unwrap();
+        writeln!(out, "      <data key=\"label\">{}</data>", escape_xml(labels.label(*v))).unwrap();
+        writeln!(out, "    </node>").unwrap();
+    }
+    for (u, v) in sorted_edges(graph) {
+        writeln!(out, "    <edge source=\"{}\" target=\"{}\"/>", u, v).unwrap();
+    }
+    for (v, r, l) in witness_paths(order, witnesses, &positions) {
+        let colour = COLOURS[positions[&v] % COLOURS.len()];
+        for end in [v, l] {
+            writeln!(out, "    <edge source=\"{}\" target=\"{}\">", r, end).unwrap();
+            writeln!(out, "      <data key=\"witness\">{}</data>", v).unwrap();
+            writeln!(out, "      <data key=\"colour\">{}</data>", colour).unwrap();
+            writeln!(out, "    </edge>").unwrap();
+        }
+    }
+    writeln!(out, "  </graph>").unwrap();
+    writeln!(out, "</graphml>").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod test_witness_export {
+    use crate::vertexLabels::read_labelled_graph;
+    use crate::witnessExport::{to_dot, to_graphml};
+    use graphbench::graph::VertexMap;
+
+    #[test]
+    fn to_dot_should_colour_witness_paths() {
+        let (graph, labels) = read_labelled_graph("a b\nb c\n".as_bytes()).unwrap();
+        let witnesses: VertexMap<Vec<(u32, u32)>> = [(0, vec![]), (2, vec![(1, 0)])].into_iter().collect();
+
+        let dot = to_dot(&graph, &labels, &[1, 2, 0], &witnesses);
+
+        assert!(dot.starts_with("digraph witnesses {\n"));
+        assert!(dot.contains("  1 [label=\"0: b\"];\n"));
+        assert!(dot.contains("  0 -> 1 [dir=none, color=grey];\n"));
+        assert!(dot.contains("  1 -> 2 [color=blue];\n"));
+        assert!(dot.contains("  1 -> 0 [color=blue];\n"));
+    }
+
+    #[test]
+    fn to_graphml_should_drop_paths_ending_before_vertex() {
+        let (graph, labels) = read_labelled_graph("a b\nb c\n".as_bytes()).unwrap();
+        let witnesses: VertexMap<Vec<(u32, u32)>> = [(2, vec![(1, 0)])].into_iter().collect();
+
+        let with_path = to_graphml(&graph, &labels, &[1, 2, 0], &witnesses);
+        let without_path = to_graphml(&graph, &labels, &[1, 0, 2], &witnesses);
+
+        assert_eq!(with_path.matches("<edge ").count(), 4);
+        assert_eq!(without_path.matches("<edge ").count(), 2);
+        assert!(with_path.contains("<data key=\"label\">c</data>"));
+    }
+}